@@ -1,36 +1,73 @@
-use anima_solver::{solve, State};
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use anima_solver::{solve, solve_ida, State};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::{fs, path::Path};
 
-fn solve_free_radical(c: &mut Criterion) {
-    const SQUARE_DANCE: &str = " ....\n.r.r.\n.. ..\n.r.r.\n.... \n\nR 2 1\nR 1 2\nR 3 2\nR 2 3";
+// IDA* re-expands states on every iteration and takes seconds on the larger levels, so it is only
+// measured on a few representative ones
+const IDA_PUZZLES: &[&str] = &["2.5-square_dance", "3.9-fractal"];
 
-    let (initial_state, data) = State::parse(SQUARE_DANCE).unwrap();
+/// Loads every bundled puzzle as a `(name, source)` pair, sorted by world and level number.
+fn bundled_puzzles() -> Vec<(String, String)> {
+    let puzzles = Path::new(env!("CARGO_MANIFEST_DIR")).join("puzzles");
 
-    c.bench_function("solve_square_dance", |b| {
-        b.iter(|| solve(black_box(&initial_state).clone(), &data))
-    });
+    let mut result = fs::read_dir(puzzles)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|e| e == "txt"))
+        .map(|path| {
+            let name = path.file_stem().unwrap().to_str().unwrap().to_string();
+            (name, fs::read_to_string(&path).unwrap())
+        })
+        .collect::<Vec<_>>();
+    result.sort_by_key(|(name, _)| level_number(name));
+    result
+}
+
+/// Splits a puzzle name like `3.10-box_step` into its world and level numbers.
+fn level_number(name: &str) -> (u32, u32) {
+    let number = name.split('-').next().unwrap();
+    let (world, level) = number.split_once('.').unwrap();
+    (world.parse().unwrap(), level.parse().unwrap())
+}
 
-    const FRACTAL: &str =
-        "  r  \n ... \n. b .\n.....\n. r .\n ... \n  b  \n\nR 1 3\nR 3 3\nB 2 2\nB 2 4";
+fn bench_world(c: &mut Criterion, world: u32, puzzles: &[(String, String)]) {
+    let mut group = c.benchmark_group(format!("world_{}", world));
 
-    let (initial_state, data) = State::parse(FRACTAL).unwrap();
+    for (name, source) in puzzles {
+        let (initial_state, data) = State::parse(source).unwrap();
 
-    c.bench_function("solve_fractal", |b| {
-        b.iter(|| solve(black_box(&initial_state).clone(), &data))
-    });
+        group.bench_function(BenchmarkId::new("parse", name), |b| {
+            b.iter(|| State::parse(black_box(source)).unwrap())
+        });
 
-    const ANTIPARTICLE: &str =
-        ".....\n.r.r.\n.....\n.r.r.\n.....\n\nR 1 2\nR 2 1\nB 2 2\nR 2 3\nR 3 2";
+        group.bench_function(BenchmarkId::new("transitions", name), |b| {
+            b.iter(|| black_box(&initial_state).transitions(&data))
+        });
 
-    let (initial_state, data) = State::parse(ANTIPARTICLE).unwrap();
+        group.bench_function(BenchmarkId::new("heuristic", name), |b| {
+            b.iter(|| black_box(&initial_state).heuristic(&data))
+        });
 
-    c.bench_function("solve_antiparticle", |b| {
-        b.iter(|| solve(black_box(&initial_state).clone(), &data))
-    });
+        group.bench_function(BenchmarkId::new("solve", name), |b| {
+            b.iter(|| solve(black_box(&initial_state).clone(), &data))
+        });
+
+        if IDA_PUZZLES.contains(&name.as_str()) {
+            group.bench_function(BenchmarkId::new("solve_ida", name), |b| {
+                b.iter(|| solve_ida(black_box(&initial_state).clone(), &data))
+            });
+        }
+    }
+
+    group.finish();
 }
 
 fn criterion_benchmark(c: &mut Criterion) {
-    solve_free_radical(c);
+    let puzzles = bundled_puzzles();
+
+    for world in puzzles.chunk_by(|a, b| level_number(&a.0).0 == level_number(&b.0).0) {
+        bench_world(c, level_number(&world[0].0).0, world);
+    }
 }
 
 criterion_group!(benches, criterion_benchmark);
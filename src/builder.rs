@@ -0,0 +1,84 @@
+use crate::{validate, Actor, Color, Data, Diagnostic, Goal, Severity, State, Tile, Vec2};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum BuildError {
+    InvalidSize {
+        size: Vec2,
+    },
+    TileOutOfBounds {
+        position: Vec2,
+    },
+    /// The puzzle failed validation. Contains every diagnostic with `Severity::Error`.
+    Invalid(Vec<Diagnostic>),
+}
+
+/// Constructs puzzles programmatically instead of parsing them from text.
+///
+/// Every tile starts out passable. Positions use the same coordinates as puzzle files, with
+/// `(0, 0)` in the bottom left corner.
+#[derive(Debug, Clone)]
+pub struct PuzzleBuilder {
+    size: Vec2,
+    tiles: Vec<Tile>,
+    goals: Vec<Goal>,
+    actors: Vec<Actor>,
+    tile_out_of_bounds: Option<Vec2>,
+}
+
+impl PuzzleBuilder {
+    pub fn new(size: Vec2) -> Self {
+        Self {
+            size,
+            tiles: vec![Tile::Passable; (size.x.max(0) * size.y.max(0)) as usize],
+            goals: Vec::new(),
+            actors: Vec::new(),
+            tile_out_of_bounds: None,
+        }
+    }
+
+    pub fn tile(&mut self, position: Vec2, tile: Tile) -> &mut Self {
+        if position.x < 0
+            || position.x >= self.size.x
+            || position.y < 0
+            || position.y >= self.size.y
+        {
+            self.tile_out_of_bounds.get_or_insert(position);
+        } else {
+            self.tiles[(position.x + position.y * self.size.x) as usize] = tile;
+        }
+        self
+    }
+
+    pub fn goal(&mut self, position: Vec2, color: Color) -> &mut Self {
+        self.goals.push(Goal::new(position, color));
+        self
+    }
+
+    pub fn actor(&mut self, position: Vec2, color: Color) -> &mut Self {
+        self.actors.push(Actor::new(position, color));
+        self
+    }
+
+    /// Builds the puzzle, failing if it has any validation errors. Warnings are ignored.
+    pub fn build(&self) -> Result<(State, Data), BuildError> {
+        if self.size.x <= 0 || self.size.y <= 0 {
+            return Err(BuildError::InvalidSize { size: self.size });
+        }
+        if let Some(position) = self.tile_out_of_bounds {
+            return Err(BuildError::TileOutOfBounds { position });
+        }
+
+        let state = State::new(self.actors.iter().cloned().collect());
+        let data = Data::new(self.size, self.tiles.clone(), self.goals.clone());
+
+        let errors = validate(&state, &data)
+            .into_iter()
+            .filter(|d| d.severity() == Severity::Error)
+            .collect::<Vec<_>>();
+        if errors.is_empty() {
+            Ok((state, data))
+        } else {
+            Err(BuildError::Invalid(errors))
+        }
+    }
+}
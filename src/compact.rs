@@ -0,0 +1,83 @@
+use crate::{Actor, Actors, Color, Data, State, Vec2};
+
+/// A state packed into a single integer, as stored by the solver.
+///
+/// Each actor is stored as the index of its cell in the board, grouped by color and in the order
+/// of the unpacked state's actors. Compact states are only meaningful together with the `Packing`
+/// that produced them.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct CompactState(u128);
+
+/// Converts the states of one puzzle to and from `CompactState`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Packing {
+    bits: u32,
+    width: i32,
+    colors: Vec<(Color, usize)>,
+}
+
+impl Packing {
+    /// Creates a packing for the states reachable from `state`. Returns `None` if they do not fit
+    /// in a `CompactState` or some actor is outside the board.
+    pub fn new(state: &State, data: &Data) -> Option<Packing> {
+        let actors = state.actors();
+        if actors.iter().any(|a| data.index(a.position()).is_none()) {
+            return None;
+        }
+
+        let cells = data.tiles().len();
+        let bits = usize::BITS - cells.saturating_sub(1).leading_zeros();
+        if bits as usize * actors.len() > u128::BITS as usize {
+            return None;
+        }
+
+        let mut colors = actors.iter().map(|a| (a.color(), 0)).collect::<Vec<_>>();
+        colors.sort();
+        colors.dedup();
+        for (color, count) in colors.iter_mut() {
+            *count = actors.iter().filter(|a| a.color() == *color).count();
+        }
+
+        Some(Packing {
+            bits,
+            width: data.size().x,
+            colors,
+        })
+    }
+
+    /// Packs a state of the puzzle this packing was created for.
+    #[inline]
+    pub fn pack(&self, state: &State) -> CompactState {
+        let mut result = 0;
+        for &(color, _) in self.colors.iter() {
+            for actor in state.actors().iter().filter(|a| a.color() == color) {
+                let position = actor.position();
+                let index = position.x + position.y * self.width;
+                result = (result << self.bits) | index as u128;
+            }
+        }
+        CompactState(result)
+    }
+
+    /// Unpacks a state, with its actors in sorted order.
+    #[inline]
+    pub fn unpack(&self, state: CompactState) -> State {
+        let mask = (1 << self.bits) - 1;
+        let mut value = state.0;
+
+        let mut actors = Actors::new();
+        for &(color, count) in self.colors.iter().rev() {
+            for _ in 0..count {
+                let index = (value & mask) as i32;
+                value >>= self.bits;
+                actors.push(Actor::new(
+                    Vec2::new(index % self.width, index / self.width),
+                    color,
+                ));
+            }
+        }
+        actors.sort();
+
+        State::new(actors)
+    }
+}
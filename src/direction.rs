@@ -10,6 +10,13 @@ pub enum Direction {
 }
 
 impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Right,
+        Direction::Up,
+        Direction::Left,
+        Direction::Down,
+    ];
+
     #[inline]
     pub fn rotate_ccw(self) -> Direction {
         match self {
@@ -40,6 +47,28 @@ impl Direction {
         }
     }
 
+    /// Returns the arrow-like character used for this direction in compact solutions.
+    #[inline]
+    pub fn to_char(self) -> char {
+        match self {
+            Direction::Right => '>',
+            Direction::Up => '^',
+            Direction::Left => '<',
+            Direction::Down => 'v',
+        }
+    }
+
+    #[inline]
+    pub fn from_char(c: char) -> Option<Direction> {
+        match c {
+            '>' => Some(Direction::Right),
+            '^' => Some(Direction::Up),
+            '<' => Some(Direction::Left),
+            'v' | 'V' => Some(Direction::Down),
+            _ => None,
+        }
+    }
+
     #[inline]
     pub fn to_vec2(self) -> Vec2 {
         match self {
@@ -62,19 +91,82 @@ impl fmt::Display for Direction {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct ParseDirectionError(String);
 
+impl ParseDirectionError {
+    /// Returns the part of the input that is not a direction.
+    pub fn token(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ParseDirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid direction '{}', expected one of 'right', 'up', 'left', 'down', 'R', 'U', 'L', 'D' or arrows like '>^<v'",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseDirectionError {}
+
 impl FromStr for Direction {
     type Err = ParseDirectionError;
 
+    /// Parses a direction name or its first letter, ignoring case.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "right" => Ok(Direction::Right),
-            "up" => Ok(Direction::Up),
-            "left" => Ok(Direction::Left),
-            "down" => Ok(Direction::Down),
+        match s.to_ascii_lowercase().as_str() {
+            "right" | "r" => Ok(Direction::Right),
+            "up" | "u" => Ok(Direction::Up),
+            "left" | "l" => Ok(Direction::Left),
+            "down" | "d" => Ok(Direction::Down),
             _ => Err(ParseDirectionError(s.to_string())),
         }
     }
 }
+
+/// Parses a sequence of moves separated by commas or whitespace.
+///
+/// Each move may be a direction name or letter as accepted by `Direction::from_str`, and runs of
+/// arrows or letters like `>>^v<` or `RRUDL` may be written without separators. This accepts both
+/// the output of `format_solution` and `format_compact`.
+pub fn parse_solution(s: &str) -> Result<Vec<Direction>, ParseDirectionError> {
+    let mut solution = Vec::new();
+    for token in s
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+    {
+        match token.parse::<Direction>() {
+            Ok(direction) => solution.push(direction),
+            Err(e) => {
+                for c in token.chars() {
+                    let direction = Direction::from_char(c)
+                        .or_else(|| c.encode_utf8(&mut [0; 4]).parse().ok())
+                        .ok_or_else(|| e.clone())?;
+                    solution.push(direction);
+                }
+            }
+        }
+    }
+    Ok(solution)
+}
+
+/// Formats moves as a comma-separated list of names, e.g. `Right, Up, Left`.
+pub fn format_solution(solution: &[Direction]) -> String {
+    solution
+        .iter()
+        .map(Direction::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Formats moves as a string of arrows, e.g. `>^<`.
+pub fn format_compact(solution: &[Direction]) -> String {
+    solution
+        .iter()
+        .map(|direction| direction.to_char())
+        .collect()
+}
@@ -1,9 +1,30 @@
+mod builder;
+mod compact;
 mod direction;
+mod manifest;
+mod play;
 mod puzzle;
+mod render;
+mod rules;
 mod solve;
+mod validate;
 mod vec2;
+mod verify;
 
+pub use builder::*;
+pub use compact::*;
 pub use direction::*;
+pub use manifest::*;
+pub use play::*;
 pub use puzzle::*;
-pub use solve::solve;
+pub use render::*;
+pub use rules::*;
+pub use solve::{
+    hint, hint_with_config, solve, solve_all, solve_all_with_config, solve_ida,
+    solve_ida_with_config, solve_with_config, AbortReason, CancellationToken, Hint,
+    OptimalSolutions, SolveAllOutcome, SolveAllResult, SolveOutcome, SolveResult, SolverConfig,
+    Statistics,
+};
+pub use validate::*;
 pub use vec2::*;
+pub use verify::*;
@@ -1,58 +1,570 @@
 use anima_solver::*;
-use std::{env, fmt, fs, io, path::Path, time::Instant};
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::{self, Write as _},
+    fs,
+    io::{self, BufRead, Write},
+    iter::Peekable,
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    process,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
-struct DisplayState<'a>(&'a State, &'a Data);
+enum Algorithm {
+    AStar,
+    Ida,
+}
 
-impl<'a> fmt::Display for DisplayState<'a> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.0.display(self.1, f)
+enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(()),
+        }
+    }
+}
+
+struct ResolutionMode(Resolution);
+
+impl FromStr for ResolutionMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pairwise" => Ok(ResolutionMode(Resolution::Pairwise)),
+            "chained" => Ok(ResolutionMode(Resolution::Chained)),
+            _ => Err(()),
+        }
     }
 }
 
 struct Settings {
+    format: Format,
     verbose: bool,
     quiet: bool,
+    axes: bool,
+    algorithm: Algorithm,
+    max_expanded: Option<usize>,
+    max_states: Option<usize>,
+    time_limit: Option<Duration>,
+    all_limit: Option<usize>,
+    jobs: usize,
+    check: Option<PathBuf>,
+    swaps: SwapCollision,
+    resolution: Resolution,
 }
 
 impl Settings {
     fn new() -> Self {
         Self {
+            format: Format::Text,
             verbose: false,
             quiet: false,
+            axes: false,
+            algorithm: Algorithm::AStar,
+            max_expanded: None,
+            max_states: None,
+            time_limit: None,
+            all_limit: None,
+            jobs: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            check: None,
+            swaps: SwapCollision::PassThrough,
+            resolution: Resolution::Pairwise,
+        }
+    }
+
+    fn rules(&self) -> StandardRules {
+        StandardRules::new()
+            .swaps(self.swaps)
+            .resolution(self.resolution)
+    }
+
+    fn solver_config(&self) -> SolverConfig {
+        SolverConfig {
+            rules: self.rules(),
+            max_expanded: self.max_expanded,
+            deadline: self.time_limit.map(|limit| Instant::now() + limit),
+            max_states: self.max_states,
+            cancellation: None,
+        }
+    }
+}
+
+struct Seconds(Duration);
+
+impl FromStr for Seconds {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let seconds = s.parse().map_err(|_| ())?;
+        Duration::try_from_secs_f64(seconds)
+            .map(Seconds)
+            .map_err(|_| ())
+    }
+}
+
+struct Swaps(SwapCollision);
+
+impl FromStr for Swaps {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pass" => Ok(Swaps(SwapCollision::PassThrough)),
+            "revert" => Ok(Swaps(SwapCollision::Revert)),
+            "block" => Ok(Swaps(SwapCollision::Block)),
+            _ => Err(()),
+        }
+    }
+}
+
+fn parse_value<T: FromStr>(flag: &str, value: Option<String>) -> Option<T> {
+    let result = value.as_deref().and_then(|v| v.parse().ok());
+    if result.is_none() {
+        eprintln!("Invalid or missing value for {}", flag);
+    }
+    result
+}
+
+/// Applies the option `flag` to `settings`, taking its value from `args`. Returns `Some(false)` if
+/// `flag` is not an option and `None` if its value is invalid.
+fn parse_flag<I: Iterator<Item = String>>(
+    flag: &str,
+    args: &mut I,
+    settings: &mut Settings,
+) -> Option<bool> {
+    match flag {
+        "-v" => settings.verbose = true,
+        "-q" => settings.quiet = true,
+        "--axes" => settings.axes = true,
+        "--ida" => settings.algorithm = Algorithm::Ida,
+        "--max-nodes" => settings.max_expanded = Some(parse_value(flag, args.next())?),
+        "--max-states" => settings.max_states = Some(parse_value(flag, args.next())?),
+        "--timeout" => {
+            let Seconds(limit) = parse_value(flag, args.next())?;
+            settings.time_limit = Some(limit);
+        }
+        "--all" => settings.all_limit = Some(parse_value(flag, args.next())?),
+        "--format" => settings.format = parse_value(flag, args.next())?,
+        "-j" | "--jobs" => {
+            settings.jobs = parse_value::<NonZeroUsize>(flag, args.next())?.get();
+        }
+        "--swaps" => {
+            let Swaps(swaps) = parse_value(flag, args.next())?;
+            settings.swaps = swaps;
+        }
+        "--resolution" => {
+            let ResolutionMode(resolution) = parse_value(flag, args.next())?;
+            settings.resolution = resolution;
         }
+        "--check" => settings.check = Some(parse_value(flag, args.next())?),
+        _ => return Some(false),
     }
+    Some(true)
+}
+
+/// Applies the options before a subcommand's arguments to `settings`. Returns `false` if one of
+/// them is invalid.
+fn parse_leading_flags<I: Iterator<Item = String>>(
+    args: &mut Peekable<I>,
+    settings: &mut Settings,
+) -> bool {
+    while let Some(flag) = args.next_if(|arg| arg.starts_with('-')) {
+        match parse_flag(&flag, args, settings) {
+            Some(true) => (),
+            Some(false) => {
+                eprintln!("Unknown option {}", flag);
+                return false;
+            }
+            None => return false,
+        }
+    }
+    true
 }
 
 pub fn execute() {
     let mut settings = Settings::new();
     let mut paths = Vec::new();
 
-    for arg in env::args().skip(1) {
-        match arg.as_str() {
-            "-v" => settings.verbose = true,
-            "-q" => settings.quiet = true,
-            _ => paths.push(arg),
+    let mut args = env::args().skip(1).peekable();
+    match args.peek().map(String::as_str) {
+        Some("play") => {
+            args.next();
+            if !parse_leading_flags(&mut args, &mut settings) {
+                return;
+            }
+            match (args.next(), args.next()) {
+                (Some(path), None) => {
+                    if let Err(e) = play_file(path.as_ref(), &settings) {
+                        eprintln!("Error while playing '{}':\n{}", path, e);
+                    }
+                }
+                _ => println!("Usage: {} play [OPTIONS] PATH", env::args().next().unwrap()),
+            }
+            return;
+        }
+        Some("hint") => {
+            args.next();
+            if !parse_leading_flags(&mut args, &mut settings) {
+                return;
+            }
+            match args.next() {
+                Some(path) => {
+                    let moves = match parse_solution(&args.collect::<Vec<_>>().join(" ")) {
+                        Ok(moves) => moves,
+                        Err(e) => {
+                            eprintln!("{}", e);
+                            return;
+                        }
+                    };
+                    if let Err(e) = hint_file(path.as_ref(), &moves, &settings) {
+                        eprintln!("Error while solving '{}':\n{}", path, e);
+                    }
+                }
+                None => println!(
+                    "Usage: {} hint [OPTIONS] PATH [MOVES]",
+                    env::args().next().unwrap()
+                ),
+            }
+            return;
+        }
+        Some("verify") => {
+            args.next();
+            if !parse_leading_flags(&mut args, &mut settings) {
+                return;
+            }
+            match args.next() {
+                Some(path) => {
+                    let moves = args.collect::<Vec<_>>();
+                    let moves = match moves.as_slice() {
+                        [file] if Path::new(file).is_file() => match fs::read_to_string(file) {
+                            Ok(moves) => moves,
+                            Err(e) => {
+                                eprintln!("Error while reading '{}':\n{}", file, e);
+                                return;
+                            }
+                        },
+                        _ => moves.join(" "),
+                    };
+                    let moves = match parse_solution(&moves) {
+                        Ok(moves) => moves,
+                        Err(e) => {
+                            eprintln!("{}", e);
+                            return;
+                        }
+                    };
+                    if let Err(e) = verify_file(path.as_ref(), &moves, &settings) {
+                        eprintln!("Error while verifying '{}':\n{}", path, e);
+                    }
+                }
+                None => println!(
+                    "Usage: {} verify [OPTIONS] PATH MOVES|MOVES_PATH",
+                    env::args().next().unwrap()
+                ),
+            }
+            return;
+        }
+        _ => (),
+    }
+
+    while let Some(arg) = args.next() {
+        match parse_flag(&arg, &mut args, &mut settings) {
+            Some(true) => (),
+            Some(false) => collect_puzzles(PathBuf::from(arg), &mut paths),
+            None => return,
+        }
+    }
+
+    // Puzzles listed in the manifest are solved after any given on the command line
+    let mut expected = vec![None; paths.len()];
+    if let Some(check) = &settings.check {
+        let manifest = match fs::read_to_string(check)
+            .map_err(|e| e.to_string())
+            .and_then(|s| Manifest::parse(&s).map_err(|e| e.to_string()))
+        {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                eprintln!("Error while reading '{}':\n{}", check.display(), e);
+                process::exit(1);
+            }
+        };
+        let directory = check.parent().unwrap_or_else(|| Path::new(""));
+        for (name, length) in manifest.entries() {
+            paths.push(directory.join(name));
+            expected.push(Some(*length));
         }
     }
 
     if paths.is_empty() {
-        println!("Usage: {} [-v -q] PATHS", env::args().next().unwrap());
-        println!("  -v       Print states along with solutions");
-        println!("  -q       Do not print solutions");
-        println!("  PATHS    A list of paths to problem files");
+        println!(
+            "Usage: {} [-v -q --axes --ida --max-nodes N --max-states N --timeout SECONDS --all N --format FORMAT -j N --check PATH --swaps MODE --resolution MODE] PATHS",
+            env::args().next().unwrap()
+        );
+        println!("  -v                   Print states along with solutions");
+        println!("  -q                   Do not print solutions");
+        println!("  --axes               Label printed states with coordinates");
+        println!("  --ida                Solve with iterative-deepening A* instead of A*");
+        println!("  --max-nodes N        Give up after expanding N states");
+        println!("  --max-states N       Give up after visiting N distinct states");
+        println!("  --timeout SECONDS    Give up after solving for SECONDS");
+        println!("  --all N              Count every optimal solution and print up to N of them");
+        println!("  --format FORMAT      Print results as 'text' (the default) or 'json', one object per line");
+        println!(
+            "  -j, --jobs N         Solve N puzzles at a time, defaults to the number of CPUs"
+        );
+        println!("  --check PATH         Solve the puzzles in the manifest at PATH and compare their solution lengths");
+        println!("  --swaps MODE         When actors swap places, 'pass' through each other (the default), 'revert' both or 'block' the move");
+        println!("  --resolution MODE    Find blocked actors 'pairwise' (the default) or 'chained', which is faster for long trains of actors");
+        println!("  PATHS                A list of paths to problem files or directories of them");
+        println!();
+        println!("       {} play [OPTIONS] PATH", env::args().next().unwrap());
+        println!("  Play the puzzle at PATH interactively, under the rules given by --swaps and --resolution");
+        println!();
+        println!(
+            "       {} hint [OPTIONS] PATH [MOVES]",
+            env::args().next().unwrap()
+        );
+        println!(
+            "  Suggest the next optimal move after playing MOVES, e.g. \"Right, Up\" or \">^\""
+        );
+        println!("  Takes the same limits and rules as solving, e.g. --timeout or --swaps");
+        println!();
+        println!(
+            "       {} verify [OPTIONS] PATH MOVES|MOVES_PATH",
+            env::args().next().unwrap()
+        );
+        println!("  Check whether MOVES, or the moves in MOVES_PATH, solve the puzzle under the given rules");
     } else {
-        for path in paths {
-            if let Err(e) = solve_file(path.as_ref(), &settings) {
-                eprintln!("Error while solving '{}':\n{:?}", path, e);
+        let now = Instant::now();
+        let statuses = solve_files(&paths, &settings);
+        let elapsed = now.elapsed();
+
+        if let Format::Text = settings.format {
+            if statuses.len() > 1 {
+                print_summary(&statuses, elapsed);
+            }
+        }
+
+        if settings.check.is_some() && !check_lengths(&paths, &statuses, &expected, &settings) {
+            process::exit(1);
+        }
+    }
+}
+
+/// Adds `path` to `puzzles`, replacing directories with the `.txt` files they contain
+/// recursively and in sorted order.
+fn collect_puzzles(path: PathBuf, puzzles: &mut Vec<PathBuf>) {
+    if !path.is_dir() {
+        puzzles.push(path);
+        return;
+    }
+
+    match fs::read_dir(&path) {
+        Ok(entries) => {
+            let mut entries = entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .collect::<Vec<_>>();
+            entries.sort();
+            for entry in entries {
+                if entry.is_dir() || entry.extension().is_some_and(|e| e == "txt") {
+                    collect_puzzles(entry, puzzles);
+                }
             }
         }
+        // Reading the directory as a puzzle reports the error in order with the other results
+        Err(_) => puzzles.push(path),
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Status {
+    /// Solved with a solution of the given length.
+    Solved(usize),
+    Unsolvable,
+    Aborted,
+    Error,
+}
+
+/// The buffered output of solving a single puzzle.
+struct Report {
+    stdout: String,
+    stderr: String,
+    status: Status,
+}
+
+fn solve_report(path: &Path, settings: &Settings) -> Report {
+    let mut stdout = String::new();
+    let mut stderr = String::new();
+    let status = match solve_file(path, settings, &mut stdout, &mut stderr) {
+        Ok(status) => status,
+        Err(e) => {
+            match settings.format {
+                Format::Text => {
+                    writeln!(stderr, "Error while solving '{}':\n{}", path.display(), e)
+                }
+                Format::Json => writeln!(
+                    stdout,
+                    "{{\"path\":{},\"outcome\":\"error\",\"error\":{}}}",
+                    json_string(&path.display().to_string()),
+                    json_string(&e.to_string())
+                ),
+            }
+            .unwrap();
+            Status::Error
+        }
+    };
+
+    Report {
+        stdout,
+        stderr,
+        status,
+    }
+}
+
+/// Solves puzzles on `settings.jobs` worker threads, printing each report in the order of `paths`
+/// as soon as it and every report before it are done.
+fn solve_files(paths: &[PathBuf], settings: &Settings) -> Vec<Status> {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..settings.jobs.min(paths.len()) {
+            let sender = sender.clone();
+            let next = &next;
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let path = match paths.get(index) {
+                    Some(path) => path,
+                    None => break,
+                };
+                if sender.send((index, solve_report(path, settings))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut statuses = Vec::with_capacity(paths.len());
+        for (index, report) in receiver {
+            pending.insert(index, report);
+            while let Some(report) = pending.remove(&statuses.len()) {
+                print!("{}", report.stdout);
+                eprint!("{}", report.stderr);
+                statuses.push(report.status);
+            }
+        }
+        statuses
+    })
+}
+
+fn print_summary(statuses: &[Status], elapsed: Duration) {
+    let count = |f: fn(&Status) -> bool| statuses.iter().filter(|s| f(s)).count();
+    println!("Summary:");
+    println!(
+        "  Solved      {}",
+        count(|s| matches!(s, Status::Solved(_)))
+    );
+    println!("  Unsolvable  {}", count(|s| *s == Status::Unsolvable));
+    println!("  Aborted     {}", count(|s| *s == Status::Aborted));
+    println!("  Errors      {}", count(|s| *s == Status::Error));
+    println!("  Total       {}", statuses.len());
+    println!(
+        "  Time        {}.{:09}s",
+        elapsed.as_secs(),
+        elapsed.subsec_nanos()
+    );
+}
+
+/// Reports every puzzle whose result differs from its expected solution length, returning whether
+/// all of them matched. The report goes to stderr when stdout is reserved for JSON.
+fn check_lengths(
+    paths: &[PathBuf],
+    statuses: &[Status],
+    expected: &[Option<usize>],
+    settings: &Settings,
+) -> bool {
+    let mut report = String::new();
+    let mut checked = 0;
+    let mut failed = 0;
+    for ((path, status), expected) in paths.iter().zip(statuses).zip(expected) {
+        let expected = match expected {
+            Some(expected) => *expected,
+            None => continue,
+        };
+        checked += 1;
+
+        let found = match status {
+            Status::Solved(length) if *length == expected => continue,
+            Status::Solved(length) => format!("a solution of length {}", length),
+            Status::Unsolvable => "no solution".to_string(),
+            Status::Aborted => "the solver aborted".to_string(),
+            Status::Error => "an error".to_string(),
+        };
+        failed += 1;
+        writeln!(
+            report,
+            "{}: expected a solution of length {} but found {}",
+            path.display(),
+            expected,
+            found
+        )
+        .unwrap();
+    }
+    writeln!(
+        report,
+        "Check: {} of {} puzzles matched",
+        checked - failed,
+        checked
+    )
+    .unwrap();
+
+    match settings.format {
+        Format::Text => print!("{}", report),
+        Format::Json => eprint!("{}", report),
     }
+    failed == 0
 }
 
 #[derive(Debug)]
 enum SolveError<T> {
     IoError(io::Error),
     ParseError(T),
+    InvalidPuzzle(Vec<Diagnostic>),
+}
+
+impl<T: fmt::Display> fmt::Display for SolveError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::IoError(e) => write!(f, "{}", e),
+            Self::ParseError(e) => write!(f, "{}", e),
+            Self::InvalidPuzzle(diagnostics) => {
+                for (i, diagnostic) in diagnostics.iter().enumerate() {
+                    if i != 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "{}: {}", diagnostic.severity(), diagnostic)?;
+                }
+                Ok(())
+            }
+        }
+    }
 }
 
 impl<T> From<io::Error> for SolveError<T> {
@@ -61,61 +573,426 @@ impl<T> From<io::Error> for SolveError<T> {
     }
 }
 
-fn solve_file(path: &Path, settings: &Settings) -> Result<(), SolveError<ParseError>> {
-    let now = Instant::now();
-    let (initial_state, data) =
-        State::parse(&fs::read_to_string(path)?).map_err(SolveError::ParseError)?;
-    let parse_elapsed = now.elapsed();
-
-    let now = Instant::now();
-    let result = solve(initial_state.clone(), &data);
-    let solve_elapsed = now.elapsed();
-
-    println!("{}:", path.to_str().unwrap());
-    println!(
+fn write_timings(out: &mut String, path: &Path, parse_elapsed: Duration, solve_elapsed: Duration) {
+    writeln!(out, "{}:", path.display()).unwrap();
+    writeln!(
+        out,
         "Parse: {}.{:09}s",
         parse_elapsed.as_secs(),
         parse_elapsed.subsec_nanos()
-    );
-    println!(
+    )
+    .unwrap();
+    writeln!(
+        out,
         "Solve: {}.{:09}s",
         solve_elapsed.as_secs(),
         solve_elapsed.subsec_nanos()
-    );
+    )
+    .unwrap();
+}
+
+/// Quotes and escapes `s` as a JSON string.
+fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => write!(result, "\\u{:04x}", c as u32).unwrap(),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn json_actions(actions: &[Direction]) -> String {
+    let actions = actions
+        .iter()
+        .map(|action| json_string(&action.to_string()))
+        .collect::<Vec<_>>();
+    format!("[{}]", actions.join(","))
+}
+
+/// Formats the fields common to every successfully parsed puzzle, without the closing brace.
+fn json_header(path: &Path, parse_elapsed: Duration, solve_elapsed: Duration) -> String {
+    format!(
+        "{{\"path\":{},\"parse_time\":{},\"solve_time\":{}",
+        json_string(&path.display().to_string()),
+        parse_elapsed.as_secs_f64(),
+        solve_elapsed.as_secs_f64()
+    )
+}
+
+/// Reads, parses and validates a puzzle, writing any warnings to `warnings`.
+fn load_puzzle_into(
+    path: &Path,
+    warnings: &mut String,
+) -> Result<(State, Data), SolveError<ParseError>> {
+    let (state, data) = State::parse(&fs::read_to_string(path)?).map_err(SolveError::ParseError)?;
+
+    let (errors, diagnostics) = validate(&state, &data)
+        .into_iter()
+        .partition::<Vec<_>, _>(|d| d.severity() == Severity::Error);
+    if !errors.is_empty() {
+        return Err(SolveError::InvalidPuzzle(errors));
+    }
+    for warning in diagnostics {
+        writeln!(
+            warnings,
+            "{}: {}: {}",
+            path.display(),
+            warning.severity(),
+            warning
+        )
+        .unwrap();
+    }
+
+    Ok((state, data))
+}
+
+/// Reads, parses and validates a puzzle, printing any warnings.
+fn load_puzzle(path: &Path) -> Result<(State, Data), SolveError<ParseError>> {
+    let mut warnings = String::new();
+    let result = load_puzzle_into(path, &mut warnings);
+    eprint!("{}", warnings);
+    result
+}
+
+fn solve_file(
+    path: &Path,
+    settings: &Settings,
+    out: &mut String,
+    warnings: &mut String,
+) -> Result<Status, SolveError<ParseError>> {
+    let now = Instant::now();
+    let (initial_state, data) = load_puzzle_into(path, warnings)?;
+    let parse_elapsed = now.elapsed();
+
+    if let Some(limit) = settings.all_limit {
+        let now = Instant::now();
+        let result = solve_all_with_config(initial_state, &data, &settings.solver_config(), limit);
+        let solve_elapsed = now.elapsed();
+        let status = match &result.outcome {
+            SolveAllOutcome::Solved(optimal) => Status::Solved(optimal.length),
+            SolveAllOutcome::Unsolvable { .. } => Status::Unsolvable,
+            SolveAllOutcome::Aborted { .. } => Status::Aborted,
+        };
+
+        if let Format::Json = settings.format {
+            let mut json = json_header(path, parse_elapsed, solve_elapsed);
+            match &result.outcome {
+                SolveAllOutcome::Solved(optimal) => {
+                    let solutions = optimal
+                        .solutions
+                        .iter()
+                        .map(|solution| json_actions(solution))
+                        .collect::<Vec<_>>();
+                    write!(
+                        json,
+                        ",\"outcome\":\"solved\",\"length\":{},\"count\":{},\"solutions\":[{}]",
+                        optimal.length,
+                        optimal.count,
+                        solutions.join(",")
+                    )
+                    .unwrap();
+                }
+                SolveAllOutcome::Unsolvable { reachable_states } => write!(
+                    json,
+                    ",\"outcome\":\"unsolvable\",\"reachable_states\":{}",
+                    reachable_states
+                )
+                .unwrap(),
+                SolveAllOutcome::Aborted { reason } => write!(
+                    json,
+                    ",\"outcome\":\"aborted\",\"reason\":{}",
+                    json_string(&reason.to_string())
+                )
+                .unwrap(),
+            }
+            writeln!(
+                out,
+                "{},\"expanded\":{},\"generated\":{}}}",
+                json, result.statistics.expanded, result.statistics.generated
+            )
+            .unwrap();
+            return Ok(status);
+        }
+
+        write_timings(out, path, parse_elapsed, solve_elapsed);
+        writeln!(
+            out,
+            "Explored {} states ({} generated)",
+            result.statistics.expanded, result.statistics.generated
+        )
+        .unwrap();
+        match result.outcome {
+            SolveAllOutcome::Solved(optimal) => {
+                writeln!(
+                    out,
+                    "Found {} optimal solutions of length {}:",
+                    optimal.count, optimal.length
+                )
+                .unwrap();
+                if !settings.quiet {
+                    for solution in optimal.solutions.iter() {
+                        writeln!(out, "{}", format_solution(solution)).unwrap();
+                    }
+                }
+            }
+            SolveAllOutcome::Unsolvable { reachable_states } => writeln!(
+                out,
+                "No solution, all {} reachable states were explored",
+                reachable_states
+            )
+            .unwrap(),
+            SolveAllOutcome::Aborted { reason } => writeln!(out, "Aborted: {}", reason).unwrap(),
+        }
+
+        return Ok(status);
+    }
+
+    let now = Instant::now();
+    let config = settings.solver_config();
+    let result = match settings.algorithm {
+        Algorithm::AStar => solve_with_config(initial_state.clone(), &data, &config),
+        Algorithm::Ida => solve_ida_with_config(initial_state.clone(), &data, &config),
+    };
+    let solve_elapsed = now.elapsed();
+    let status = match &result.outcome {
+        SolveOutcome::Solved(solution) => Status::Solved(solution.len()),
+        SolveOutcome::Unsolvable { .. } => Status::Unsolvable,
+        SolveOutcome::Aborted { .. } => Status::Aborted,
+    };
+
+    if let Format::Json = settings.format {
+        let mut json = json_header(path, parse_elapsed, solve_elapsed);
+        match &result.outcome {
+            SolveOutcome::Solved(solution) => write!(
+                json,
+                ",\"outcome\":\"solved\",\"length\":{},\"moves\":{}",
+                solution.len(),
+                json_actions(solution)
+            ),
+            SolveOutcome::Unsolvable { reachable_states } => write!(
+                json,
+                ",\"outcome\":\"unsolvable\",\"reachable_states\":{}",
+                reachable_states
+            ),
+            SolveOutcome::Aborted { reason } => write!(
+                json,
+                ",\"outcome\":\"aborted\",\"reason\":{}",
+                json_string(&reason.to_string())
+            ),
+        }
+        .unwrap();
+        writeln!(
+            out,
+            "{},\"expanded\":{},\"generated\":{}}}",
+            json, result.statistics.expanded, result.statistics.generated
+        )
+        .unwrap();
+        return Ok(status);
+    }
+
+    write_timings(out, path, parse_elapsed, solve_elapsed);
+    writeln!(
+        out,
+        "Explored {} states ({} generated)",
+        result.statistics.expanded, result.statistics.generated
+    )
+    .unwrap();
 
     if !settings.quiet {
-        if let Some(solution) = result {
-            println!("Found solution of length {}:", solution.len());
-
-            if settings.verbose {
-                let mut state = initial_state;
-                for action in solution {
-                    println!("{}", DisplayState(&state, &data));
-                    println!("{}", action);
-                    if let Transition::Indeterminate(s) =
-                        IntoIterator::into_iter(state.transitions(&data))
-                            .find(|(a, _)| a == &action)
-                            .unwrap()
-                            .1
-                    {
-                        state = s;
+        match result.outcome {
+            SolveOutcome::Solved(solution) => {
+                writeln!(out, "Found solution of length {}:", solution.len()).unwrap();
+
+                if settings.verbose {
+                    let renderer = Renderer::new().axes(settings.axes);
+                    let mut state = initial_state;
+                    for action in solution {
+                        writeln!(out, "{}", renderer.to_string(&state, &data)).unwrap();
+                        writeln!(out, "{}", action).unwrap();
+                        if let Transition::Indeterminate(s) = IntoIterator::into_iter(
+                            state.transitions_with(&data, &settings.rules()),
+                        )
+                        .find(|(a, _)| a == &action)
+                        .unwrap()
+                        .1
+                        {
+                            state = s;
+                        }
                     }
+                } else {
+                    writeln!(out, "{}", format_solution(&solution)).unwrap();
                 }
-            } else {
-                let mut actions = solution.iter();
-                if let Some(action) = actions.next() {
-                    print!("{}", action);
+            }
+            SolveOutcome::Unsolvable { reachable_states } => writeln!(
+                out,
+                "No solution, all {} reachable states were explored",
+                reachable_states
+            )
+            .unwrap(),
+            SolveOutcome::Aborted { reason } => writeln!(out, "Aborted: {}", reason).unwrap(),
+        }
+    }
+
+    Ok(status)
+}
+
+fn hint_file(
+    path: &Path,
+    moves: &[Direction],
+    settings: &Settings,
+) -> Result<(), SolveError<ParseError>> {
+    let (initial_state, data) = load_puzzle(path)?;
+    let config = settings.solver_config();
+    let state = moves.iter().fold(initial_state, |state, &direction| {
+        state.transition_with(&data, &config.rules, direction)
+    });
+
+    match hint_with_config(&state, &data, &config) {
+        Hint::Move {
+            direction,
+            distance,
+        } => println!(
+            "Next move: {} (distance to solution: {})",
+            direction, distance
+        ),
+        Hint::Solved => println!("The puzzle is already solved"),
+        Hint::Unsolvable => println!("No solution from this state"),
+        Hint::Aborted { reason } => println!("Aborted: {}", reason),
+    }
+
+    Ok(())
+}
+
+fn verify_file(
+    path: &Path,
+    moves: &[Direction],
+    settings: &Settings,
+) -> Result<(), SolveError<ParseError>> {
+    let (initial_state, data) = load_puzzle(path)?;
+
+    match verify_with_rules(&initial_state, &data, &settings.rules(), moves) {
+        VerifyResult::Solved {
+            step,
+            extra_moves: 0,
+        } => println!("Solved after {} moves", step),
+        VerifyResult::Solved { step, extra_moves } => println!(
+            "Solved after {} moves, followed by {} extra moves",
+            step, extra_moves
+        ),
+        VerifyResult::Incomplete => println!("Not solved after {} moves", moves.len()),
+    }
+
+    Ok(())
+}
+
+enum Input {
+    Moves(Vec<Direction>),
+    Undo,
+    Redo,
+    Restart,
+    Quit,
+}
+
+fn parse_input(line: &str) -> Result<Input, char> {
+    match line {
+        "u" | "undo" => return Ok(Input::Undo),
+        "r" | "redo" => return Ok(Input::Redo),
+        "restart" => return Ok(Input::Restart),
+        "q" | "quit" => return Ok(Input::Quit),
+        _ => (),
+    }
+
+    let mut moves = Vec::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        let direction = match c {
+            'w' | 'W' => Direction::Up,
+            'a' | 'A' => Direction::Left,
+            's' | 'S' => Direction::Down,
+            'd' | 'D' => Direction::Right,
+            // Arrow keys arrive as ANSI escape sequences when the terminal is in line mode
+            '\x1b' => match (chars.next(), chars.next()) {
+                (Some('['), Some('A')) => Direction::Up,
+                (Some('['), Some('B')) => Direction::Down,
+                (Some('['), Some('C')) => Direction::Right,
+                (Some('['), Some('D')) => Direction::Left,
+                _ => return Err(c),
+            },
+            c if c.is_whitespace() => continue,
+            _ => return Err(c),
+        };
+        moves.push(direction);
+    }
+
+    Ok(Input::Moves(moves))
+}
+
+fn play<R: Rules, I: BufRead, W: Write>(
+    mut game: Game<R>,
+    input: I,
+    mut output: W,
+) -> io::Result<()> {
+    let renderer = Renderer::new().axes(true);
+    let mut lines = input.lines();
+
+    writeln!(
+        output,
+        "Move with WASD or the arrow keys and press enter. Other commands: undo (u), redo (r), restart, quit (q)"
+    )?;
+    loop {
+        write!(output, "{}", renderer.to_string(game.state(), game.data()))?;
+        if game.is_solved() {
+            writeln!(output, "Solved in {} moves!", game.moves().len())?;
+        }
+        write!(output, "> ")?;
+        output.flush()?;
+
+        let line = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
+        match parse_input(line.trim()) {
+            Ok(Input::Moves(moves)) => {
+                for direction in moves {
+                    game.play(direction);
+                }
+            }
+            Ok(Input::Undo) => {
+                if !game.undo() {
+                    writeln!(output, "Nothing to undo")?;
                 }
-                for action in actions {
-                    print!(", {}", action);
+            }
+            Ok(Input::Redo) => {
+                if !game.redo() {
+                    writeln!(output, "Nothing to redo")?;
                 }
-                println!();
             }
-        } else {
-            println!("No solution");
+            Ok(Input::Restart) => game.restart(),
+            Ok(Input::Quit) => break,
+            Err(c) => writeln!(output, "Unrecognized input {:?}", c)?,
         }
     }
 
+    writeln!(output)
+}
+
+fn play_file(path: &Path, settings: &Settings) -> Result<(), SolveError<ParseError>> {
+    let (initial_state, data) = load_puzzle(path)?;
+    let stdin = io::stdin();
+    play(
+        Game::with_rules(initial_state, data, settings.rules()),
+        stdin.lock(),
+        io::stdout().lock(),
+    )?;
     Ok(())
 }
 
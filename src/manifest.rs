@@ -0,0 +1,123 @@
+use std::{error::Error, fmt};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ManifestError {
+    ExpectedName { line_number: usize, line: String },
+    ExpectedEquals { line_number: usize, line: String },
+    InvalidLength { line_number: usize, line: String },
+    DuplicateName { line_number: usize, name: String },
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManifestError::ExpectedName { line_number, line } => write!(
+                f,
+                "line {}: expected a bare or quoted puzzle name: {}",
+                line_number, line
+            ),
+            ManifestError::ExpectedEquals { line_number, line } => {
+                write!(
+                    f,
+                    "line {}: expected '=' after the name: {}",
+                    line_number, line
+                )
+            }
+            ManifestError::InvalidLength { line_number, line } => write!(
+                f,
+                "line {}: expected a solution length after '=': {}",
+                line_number, line
+            ),
+            ManifestError::DuplicateName { line_number, name } => {
+                write!(
+                    f,
+                    "line {}: '{}' is listed more than once",
+                    line_number, name
+                )
+            }
+        }
+    }
+}
+
+impl Error for ManifestError {}
+
+/// The expected optimal solution length of each puzzle in a level pack.
+///
+/// Manifests are written in a small subset of TOML: one `name = length` pair per line, where the
+/// name is either bare or quoted and is the puzzle's path relative to the manifest. Blank lines
+/// and `#` comments are ignored.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Manifest {
+    entries: Vec<(String, usize)>,
+}
+
+impl Manifest {
+    pub fn parse(s: &str) -> Result<Self, ManifestError> {
+        let mut entries = Vec::new();
+
+        for (line, line_number) in s.lines().zip(1..) {
+            let rest = line.trim_start();
+            if rest.is_empty() || rest.starts_with('#') {
+                continue;
+            }
+
+            let expected_name = || ManifestError::ExpectedName {
+                line_number,
+                line: line.to_string(),
+            };
+            let (name, rest) = if let Some(quoted) = rest.strip_prefix('"') {
+                let end = quoted.find('"').ok_or_else(expected_name)?;
+                (&quoted[..end], &quoted[end + 1..])
+            } else {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+                    .unwrap_or(rest.len());
+                (&rest[..end], &rest[end..])
+            };
+            if name.is_empty() {
+                return Err(expected_name());
+            }
+
+            let rest = rest.trim_start().strip_prefix('=').ok_or_else(|| {
+                ManifestError::ExpectedEquals {
+                    line_number,
+                    line: line.to_string(),
+                }
+            })?;
+            let value = match rest.find('#') {
+                Some(comment) => &rest[..comment],
+                None => rest,
+            };
+            let length = value
+                .trim()
+                .parse()
+                .map_err(|_| ManifestError::InvalidLength {
+                    line_number,
+                    line: line.to_string(),
+                })?;
+
+            if entries.iter().any(|(n, _)| n == name) {
+                return Err(ManifestError::DuplicateName {
+                    line_number,
+                    name: name.to_string(),
+                });
+            }
+            entries.push((name.to_string(), length));
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Returns each puzzle name and its expected solution length, in the order they were listed.
+    #[inline]
+    pub fn entries(&self) -> &[(String, usize)] {
+        &self.entries
+    }
+
+    pub fn get(&self, name: &str) -> Option<usize> {
+        self.entries
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, length)| *length)
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{Data, Direction, Rules, StandardRules, State};
+
+/// An interactive play session with undo and redo.
+#[derive(Debug, Clone)]
+pub struct Game<R = StandardRules> {
+    data: Data,
+    rules: R,
+    history: Vec<State>,
+    moves: Vec<Direction>,
+    undone: Vec<Direction>,
+}
+
+impl Game {
+    pub fn new(initial_state: State, data: Data) -> Self {
+        Self::with_rules(initial_state, data, StandardRules::new())
+    }
+}
+
+impl<R: Rules> Game<R> {
+    pub fn with_rules(initial_state: State, data: Data, rules: R) -> Self {
+        Self {
+            data,
+            rules,
+            history: vec![initial_state],
+            moves: Vec::new(),
+            undone: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn data(&self) -> &Data {
+        &self.data
+    }
+
+    #[inline]
+    pub fn state(&self) -> &State {
+        self.history.last().unwrap()
+    }
+
+    /// The moves made since the start of the game, excluding any that were undone.
+    #[inline]
+    pub fn moves(&self) -> &[Direction] {
+        &self.moves
+    }
+
+    #[inline]
+    pub fn is_solved(&self) -> bool {
+        self.rules.is_solved(self.state(), &self.data)
+    }
+
+    fn push(&mut self, direction: Direction) {
+        let state = self
+            .state()
+            .transition_with(&self.data, &self.rules, direction);
+        self.history.push(state);
+        self.moves.push(direction);
+    }
+
+    /// Makes a move, discarding any moves that could have been redone.
+    pub fn play(&mut self, direction: Direction) {
+        self.undone.clear();
+        self.push(direction);
+    }
+
+    /// Takes back the last move. Returns `false` if there was nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        match self.moves.pop() {
+            Some(direction) => {
+                self.history.pop();
+                self.undone.push(direction);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Makes the last undone move again. Returns `false` if there was nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        match self.undone.pop() {
+            Some(direction) => {
+                self.push(direction);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns to the initial state. The moves made so far can be brought back with `redo`.
+    pub fn restart(&mut self) {
+        while self.undo() {}
+    }
+}
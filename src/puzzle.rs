@@ -1,46 +1,431 @@
-use crate::{Direction, Vec2};
+use crate::{Direction, Renderer, Rules, StandardRules, Vec2};
 use arrayvec::ArrayVec;
-use core::{fmt, num::ParseIntError};
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    iter::{self, FromIterator},
+    mem,
+    num::ParseIntError,
+    ops::{Deref, DerefMut},
+};
+use std::{borrow::Cow, collections::VecDeque};
+
+/// The number of actors stored inline before falling back to a heap allocation.
+const INLINE_ACTORS: usize = 8;
+
+/// The actors of a state. Puzzles with few actors store them inline so that transitions never
+/// allocate, and larger puzzles fall back to the heap.
+#[derive(Clone)]
+pub(crate) enum Actors {
+    Inline(ArrayVec<Actor, INLINE_ACTORS>),
+    Heap(Vec<Actor>),
+}
+
+impl Actors {
+    pub(crate) fn new() -> Self {
+        Actors::Inline(ArrayVec::new())
+    }
+
+    pub(crate) fn push(&mut self, actor: Actor) {
+        match self {
+            Actors::Inline(actors) => {
+                if let Err(e) = actors.try_push(actor) {
+                    let mut heap = actors.to_vec();
+                    heap.push(e.element());
+                    *self = Actors::Heap(heap);
+                }
+            }
+            Actors::Heap(actors) => actors.push(actor),
+        }
+    }
+}
+
+impl FromIterator<Actor> for Actors {
+    fn from_iter<I: IntoIterator<Item = Actor>>(iter: I) -> Self {
+        let mut result = Actors::new();
+        for actor in iter {
+            result.push(actor);
+        }
+        result
+    }
+}
+
+impl Deref for Actors {
+    type Target = [Actor];
+
+    #[inline]
+    fn deref(&self) -> &[Actor] {
+        match self {
+            Actors::Inline(actors) => actors,
+            Actors::Heap(actors) => actors,
+        }
+    }
+}
+
+impl DerefMut for Actors {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [Actor] {
+        match self {
+            Actors::Inline(actors) => actors,
+            Actors::Heap(actors) => actors,
+        }
+    }
+}
+
+impl fmt::Debug for Actors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl PartialEq for Actors {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for Actors {}
+
+impl Hash for Actors {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
 
 #[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-enum Color {
+pub enum Color {
+    /// Moves in the direction of the move.
     Red,
+    /// Moves in the opposite direction of the move.
     Blue,
+    /// Moves in the direction of the move rotated clockwise.
+    Green,
+    /// Moves in the direction of the move rotated counterclockwise.
+    Yellow,
+}
+
+impl Color {
+    pub const ALL: [Color; 4] = [Color::Red, Color::Blue, Color::Green, Color::Yellow];
+
+    /// The character used for actors of this color in puzzle files and boards.
+    #[inline]
+    pub fn actor_char(self) -> char {
+        match self {
+            Color::Red => 'R',
+            Color::Blue => 'B',
+            Color::Green => 'G',
+            Color::Yellow => 'Y',
+        }
+    }
+
+    /// The character used for goals of this color in puzzle files and boards.
+    #[inline]
+    pub fn goal_char(self) -> char {
+        match self {
+            Color::Red => 'r',
+            Color::Blue => 'b',
+            Color::Green => 'g',
+            Color::Yellow => 'y',
+        }
+    }
+
+    #[inline]
+    pub fn from_actor_char(c: char) -> Option<Color> {
+        IntoIterator::into_iter(Color::ALL).find(|color| color.actor_char() == c)
+    }
+
+    #[inline]
+    pub fn from_goal_char(c: char) -> Option<Color> {
+        IntoIterator::into_iter(Color::ALL).find(|color| color.goal_char() == c)
+    }
+}
+
+/// Formats a list of characters as `'a', 'b', 'c'` for error messages.
+fn quoted_chars(chars: impl Iterator<Item = char>) -> String {
+    chars
+        .map(|c| format!("'{}'", c))
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-enum Tile {
+pub enum Tile {
     Passable,
     Impassable,
+    /// Actors that move onto ice keep sliding in the same direction, one tile at a time, until
+    /// they are blocked by a tile or another actor.
+    Ice,
+    /// Can only be entered by actors moving in the given direction.
+    OneWay(Direction),
+    /// Sends actors that move onto it to the other teleporter with the same number, from 0 to 9.
+    /// Teleporters without exactly one partner behave like passable tiles.
+    Teleporter(u8),
 }
 
-struct Goal {
+impl Tile {
+    /// The character for the tile in puzzle files.
+    #[inline]
+    pub fn to_char(self) -> char {
+        match self {
+            Tile::Passable => '.',
+            Tile::Impassable => ' ',
+            Tile::Ice => '~',
+            Tile::OneWay(direction) => direction.to_char(),
+            Tile::Teleporter(number) => char::from_digit(number.into(), 10).unwrap_or('?'),
+        }
+    }
+
+    #[inline]
+    pub fn from_char(c: char) -> Option<Tile> {
+        match c {
+            '.' => Some(Tile::Passable),
+            ' ' => Some(Tile::Impassable),
+            '~' => Some(Tile::Ice),
+            '>' | '^' | '<' | 'v' => Direction::from_char(c).map(Tile::OneWay),
+            _ => c.to_digit(10).map(|number| Tile::Teleporter(number as u8)),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Goal {
     position: Vec2,
     color: Color,
 }
 
+impl Goal {
+    #[inline]
+    pub fn new(position: Vec2, color: Color) -> Goal {
+        Goal { position, color }
+    }
+
+    #[inline]
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    #[inline]
+    pub fn color(&self) -> Color {
+        self.color
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Data {
     size: Vec2,
     tiles: Vec<Tile>,
     goals: Vec<Goal>,
+    teleporters: Vec<(u8, Vec2)>,
+    steps: Vec<[Vec2; 4]>,
+    has_ice: bool,
+    goal_distances: Vec<Vec<usize>>,
 }
 
 impl Data {
+    pub(crate) fn new(size: Vec2, tiles: Vec<Tile>, mut goals: Vec<Goal>) -> Data {
+        // Keep goals in a fixed order so puzzles compare equal however they were built
+        goals.sort_by_key(|g| (g.position, g.color));
+
+        let mut result = Data {
+            size,
+            tiles,
+            goals,
+            teleporters: Vec::new(),
+            steps: Vec::new(),
+            has_ice: false,
+            goal_distances: Vec::new(),
+        };
+        for (index, tile) in result.tiles.iter().enumerate() {
+            if let Tile::Teleporter(number) = *tile {
+                let index = index as i32;
+                let position = Vec2::new(index % size.x, index / size.x);
+                result.teleporters.push((number, position));
+            }
+        }
+        result.has_ice = result.tiles.contains(&Tile::Ice);
+        result.steps = (0..result.tiles.len() as i32)
+            .map(|index| {
+                let position = Vec2::new(index % size.x, index / size.x);
+                Direction::ALL.map(|direction| result.compute_step(position, direction))
+            })
+            .collect();
+
+        let sources = result.sources();
+        result.goal_distances = result
+            .goals
+            .iter()
+            .map(|g| result.distances_to(g.position, &sources))
+            .collect();
+        result
+    }
+
     #[inline]
-    fn tile(&self, position: Vec2) -> Tile {
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    /// Returns the tiles row by row, starting from the bottom row (`y = 0`).
+    #[inline]
+    pub fn tiles(&self) -> &[Tile] {
+        &self.tiles
+    }
+
+    /// Returns the goals, sorted by position.
+    #[inline]
+    pub fn goals(&self) -> &[Goal] {
+        &self.goals
+    }
+
+    #[inline]
+    pub(crate) fn index(&self, position: Vec2) -> Option<usize> {
         if position.x < 0
             || position.x >= self.size.x
             || position.y < 0
             || position.y >= self.size.y
         {
-            Tile::Impassable
+            None
         } else {
-            self.tiles[(position.x + position.y * self.size.x) as usize]
+            Some((position.x + position.y * self.size.x) as usize)
+        }
+    }
+
+    /// Returns the other teleporter with the given number, if the one at `position` has exactly one
+    /// partner.
+    fn partner(&self, number: u8, position: Vec2) -> Option<Vec2> {
+        let mut others = self
+            .teleporters
+            .iter()
+            .filter(|&&(n, p)| n == number && p != position);
+        match (others.next(), others.next()) {
+            (Some(&(_, partner)), None) => Some(partner),
+            _ => None,
+        }
+    }
+
+    /// Returns the tile an actor at `position` moves onto when it takes a single step in
+    /// `direction`, ignoring other actors. Actors that step onto ice keep stepping in the same
+    /// direction during the same move.
+    #[inline]
+    pub fn step(&self, position: Vec2, direction: Direction) -> Vec2 {
+        match self.index(position) {
+            Some(index) => self.steps[index][direction as usize],
+            None => self.compute_step(position, direction),
+        }
+    }
+
+    /// Computes `step` without the table of steps on the board.
+    fn compute_step(&self, position: Vec2, direction: Direction) -> Vec2 {
+        // Every tile next to an actor far off the board is impassable, and stepping could overflow
+        if position.x < -1
+            || position.x > self.size.x
+            || position.y < -1
+            || position.y > self.size.y
+        {
+            return position;
+        }
+        let next_position = position + direction.to_vec2();
+        match self.tile(next_position) {
+            Tile::Passable | Tile::Ice => next_position,
+            Tile::Impassable => position,
+            Tile::OneWay(entry) if entry == direction => next_position,
+            Tile::OneWay(_) => position,
+            Tile::Teleporter(number) => {
+                self.partner(number, next_position).unwrap_or(next_position)
+            }
+        }
+    }
+
+    /// Returns every tile an actor at `position` can stop on when it moves in `direction`: each
+    /// tile of its slide over ice, where another actor may stop it, and the tile the slide ends on.
+    fn stops(&self, position: Vec2, direction: Direction) -> impl Iterator<Item = Vec2> + '_ {
+        let mut position = position;
+        let mut sliding = true;
+        iter::from_fn(move || {
+            let next_position = self.step(position, direction);
+            if !sliding || next_position == position {
+                return None;
+            }
+            sliding = self.tile(next_position) == Tile::Ice;
+            position = next_position;
+            Some(next_position)
+        })
+    }
+
+    /// Lists, for every tile, the tiles an actor can move onto it from in a single move.
+    fn sources(&self) -> Vec<Vec<usize>> {
+        let mut sources = vec![Vec::new(); self.tiles.len()];
+        for index in 0..self.tiles.len() {
+            let position = Vec2::new(index as i32 % self.size.x, index as i32 / self.size.x);
+            for direction in Direction::ALL {
+                for stop in self.stops(position, direction) {
+                    let stop = self.index(stop).unwrap();
+                    if !sources[stop].contains(&index) {
+                        sources[stop].push(index);
+                    }
+                }
+            }
+        }
+        sources
+    }
+
+    /// Computes the number of moves needed to reach the target from every tile, ignoring other
+    /// actors. Every color moves over the same tiles, only in a different direction for each move,
+    /// so one table serves all colors. Moves can slide or teleport actors, and other actors can
+    /// stop a slide early, so the search follows `sources` backwards from the target.
+    fn distances_to(&self, target: Vec2, sources: &[Vec<usize>]) -> Vec<usize> {
+        let mut distances = vec![usize::MAX; self.tiles.len()];
+        let mut queue = VecDeque::new();
+
+        if let Some(index) = self.index(target) {
+            if self.tiles[index] != Tile::Impassable {
+                distances[index] = 0;
+                queue.push_back(index);
+            }
+        }
+
+        while let Some(index) = queue.pop_front() {
+            let distance = distances[index] + 1;
+            for &source in sources[index].iter() {
+                if distances[source] == usize::MAX {
+                    distances[source] = distance;
+                    queue.push_back(source);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Returns a lower bound on the number of moves an actor at `position` needs to reach the goal
+    /// with the given index, or `usize::MAX` if it can never reach it.
+    #[inline]
+    pub(crate) fn goal_distance(&self, goal_index: usize, position: Vec2) -> usize {
+        match self.index(position) {
+            Some(index) => self.goal_distances[goal_index][index],
+            // Actors placed off the board can still step back onto it
+            None => Direction::ALL
+                .iter()
+                .flat_map(|&direction| self.stops(position, direction))
+                .filter_map(|stop| self.index(stop))
+                .map(|index| self.goal_distances[goal_index][index].saturating_add(1))
+                .min()
+                .unwrap_or(usize::MAX),
+        }
+    }
+
+    /// Returns the tile at the given position, treating everything outside the board as impassable.
+    #[inline]
+    pub fn tile(&self, position: Vec2) -> Tile {
+        match self.index(position) {
+            Some(index) => self.tiles[index],
+            None => Tile::Impassable,
         }
     }
 
     #[inline]
-    fn is_solved_by(&self, state: &State) -> bool {
+    pub fn is_solved_by(&self, state: &State) -> bool {
         self.goals.iter().all(|g| {
             state
                 .actors
@@ -50,12 +435,29 @@ impl Data {
     }
 }
 
-#[derive(Debug, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
-struct Actor {
+#[derive(Debug, Copy, Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Actor {
     position: Vec2,
     color: Color,
 }
 
+impl Actor {
+    #[inline]
+    pub fn new(position: Vec2, color: Color) -> Actor {
+        Actor { position, color }
+    }
+
+    #[inline]
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    #[inline]
+    pub fn color(&self) -> Color {
+        self.color
+    }
+}
+
 #[derive(Debug, Clone, Eq, Hash, PartialEq)]
 pub enum Transition<T> {
     Success,
@@ -64,21 +466,24 @@ pub enum Transition<T> {
 
 #[derive(Debug, Clone, Eq, Hash, PartialEq)]
 pub struct State {
-    actors: ArrayVec<Actor, 8>,
+    actors: Actors,
 }
 
+/// An error encountered while parsing a puzzle. Line and column numbers are one-based.
 #[derive(Debug)]
 pub enum ParseError {
     NoRows,
     NoLineBreakAfterRows,
     UnevenRows {
         line_number: usize,
+        line: String,
         data_width: usize,
         line_width: usize,
     },
     UnexpectedCharacter {
         line_number: usize,
         column_number: usize,
+        line: String,
         character: char,
     },
     EmptyActorDefinition {
@@ -86,52 +491,202 @@ pub enum ParseError {
     },
     InvalidActorColor {
         line_number: usize,
+        column_number: usize,
+        line: String,
         color: String,
     },
     MissingActorX {
         line_number: usize,
+        line: String,
     },
     MissingActorY {
         line_number: usize,
+        line: String,
     },
     InvalidActorX {
         line_number: usize,
+        column_number: usize,
+        line: String,
         parse_error: ParseIntError,
     },
     InvalidActorY {
         line_number: usize,
+        column_number: usize,
+        line: String,
         parse_error: ParseIntError,
     },
 }
 
+impl ParseError {
+    /// Returns the line number, column number and contents of the offending line, if any.
+    fn location(&self) -> Option<(usize, usize, &str)> {
+        match self {
+            ParseError::NoRows | ParseError::NoLineBreakAfterRows => None,
+            ParseError::UnevenRows {
+                line_number,
+                line,
+                data_width,
+                line_width,
+            } => Some((*line_number, usize::min(*data_width, *line_width) + 1, line)),
+            ParseError::EmptyActorDefinition { line_number } => Some((*line_number, 1, "")),
+            ParseError::MissingActorX { line_number, line }
+            | ParseError::MissingActorY { line_number, line } => {
+                Some((*line_number, line.chars().count() + 1, line))
+            }
+            ParseError::UnexpectedCharacter {
+                line_number,
+                column_number,
+                line,
+                ..
+            }
+            | ParseError::InvalidActorColor {
+                line_number,
+                column_number,
+                line,
+                ..
+            }
+            | ParseError::InvalidActorX {
+                line_number,
+                column_number,
+                line,
+                ..
+            }
+            | ParseError::InvalidActorY {
+                line_number,
+                column_number,
+                line,
+                ..
+            } => Some((*line_number, *column_number, line)),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let location = self.location();
+        if let Some((line_number, column_number, _)) = location {
+            write!(f, "line {}, column {}: ", line_number, column_number)?;
+        }
+
+        match self {
+            ParseError::NoRows => write!(f, "the puzzle is empty"),
+            ParseError::NoLineBreakAfterRows => {
+                write!(f, "missing blank line between the rows and the actors")
+            }
+            ParseError::UnevenRows {
+                data_width,
+                line_width,
+                ..
+            } => write!(
+                f,
+                "row is {} characters wide but the first row is {} characters wide",
+                line_width, data_width
+            ),
+            ParseError::UnexpectedCharacter { character, .. } => write!(
+                f,
+                "unexpected character '{}', expected one of {} or a teleporter digit",
+                character,
+                quoted_chars(
+                    ['.', ' ', '~', '>', '^', '<', 'v']
+                        .iter()
+                        .copied()
+                        .chain(Color::ALL.iter().map(|c| c.goal_char()))
+                )
+            ),
+            ParseError::EmptyActorDefinition { .. } => write!(f, "empty actor definition"),
+            ParseError::InvalidActorColor { color, .. } => write!(
+                f,
+                "invalid actor color '{}', expected one of {}",
+                color,
+                quoted_chars(Color::ALL.iter().map(|c| c.actor_char()))
+            ),
+            ParseError::MissingActorX { .. } => write!(f, "missing actor x coordinate"),
+            ParseError::MissingActorY { .. } => write!(f, "missing actor y coordinate"),
+            ParseError::InvalidActorX { parse_error, .. } => {
+                write!(f, "invalid actor x coordinate: {}", parse_error)
+            }
+            ParseError::InvalidActorY { parse_error, .. } => {
+                write!(f, "invalid actor y coordinate: {}", parse_error)
+            }
+        }?;
+
+        // Point at the offending column underneath a copy of the line
+        if let Some((line_number, column_number, line)) = location {
+            let gutter = line_number.to_string().len();
+            writeln!(f)?;
+            writeln!(f, "{:gutter$} |", "", gutter = gutter)?;
+            writeln!(f, "{} | {}", line_number, line)?;
+            write!(
+                f,
+                "{:gutter$} | {:column$}^",
+                "",
+                "",
+                gutter = gutter,
+                column = column_number - 1
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseError::InvalidActorX { parse_error, .. }
+            | ParseError::InvalidActorY { parse_error, .. } => Some(parse_error),
+            _ => None,
+        }
+    }
+}
+
 impl State {
+    pub(crate) fn new(actors: Actors) -> State {
+        State { actors }
+    }
+
+    #[inline]
+    pub fn actors(&self) -> &[Actor] {
+        &self.actors
+    }
+
+    /// Moves every actor under the standard rules.
     #[inline]
     pub fn transition(&self, data: &Data, direction: Direction) -> State {
+        self.transition_with(data, &StandardRules::new(), direction)
+    }
+
+    #[inline]
+    pub fn transition_with<R: Rules>(&self, data: &Data, rules: &R, direction: Direction) -> State {
         let mut result = self.clone();
 
         for actor in result.actors.iter_mut() {
-            let next_position = match actor.color {
-                Color::Red => actor.position + direction.to_vec2(),
-                Color::Blue => actor.position - direction.to_vec2(),
-            };
-
-            if data.tile(next_position) == Tile::Passable {
-                actor.position = next_position;
-            }
+            actor.position = data.step(actor.position, rules.movement(actor.color, direction));
         }
 
-        let mut done = false;
-        while !done {
-            done = true;
-            for i in 0..result.actors.len() {
-                for j in i + 1..result.actors.len() {
-                    if result.actors[i].position == result.actors[j].position {
-                        result.actors[i].position = self.actors[i].position;
-                        result.actors[j].position = self.actors[j].position;
-                        done = false;
-                    }
+        rules.resolve_collisions(&self.actors, &mut result.actors);
+
+        // Actors that moved onto ice keep sliding a tile at a time, so they stop at other actors
+        let slides = |actor: &Actor, before: &Actor| {
+            actor.position != before.position && data.tile(actor.position) == Tile::Ice
+        };
+        let mut previous = Cow::Borrowed(&self.actors);
+        while data.has_ice
+            && result
+                .actors
+                .iter()
+                .zip(previous.iter())
+                .any(|(a, b)| slides(a, b))
+        {
+            let mut next = result.actors.clone();
+            for (actor, before) in next.iter_mut().zip(previous.iter()) {
+                if slides(actor, before) {
+                    actor.position =
+                        data.step(actor.position, rules.movement(actor.color, direction));
                 }
             }
+            rules.resolve_collisions(&result.actors, &mut next);
+            previous = Cow::Owned(mem::replace(&mut result.actors, next));
         }
 
         result.actors.sort();
@@ -139,15 +694,17 @@ impl State {
     }
 
     pub fn transitions(&self, data: &Data) -> [(Direction, Transition<Self>); 4] {
-        [
-            Direction::Right,
-            Direction::Up,
-            Direction::Left,
-            Direction::Down,
-        ]
-        .map(|direction| {
-            let state = self.transition(data, direction);
-            if data.is_solved_by(&state) {
+        self.transitions_with(data, &StandardRules::new())
+    }
+
+    pub fn transitions_with<R: Rules>(
+        &self,
+        data: &Data,
+        rules: &R,
+    ) -> [(Direction, Transition<Self>); 4] {
+        Direction::ALL.map(|direction| {
+            let state = self.transition_with(data, rules, direction);
+            if rules.is_solved(&state, data) {
                 (direction, Transition::Success)
             } else {
                 (direction, Transition::Indeterminate(state))
@@ -155,14 +712,17 @@ impl State {
         })
     }
 
+    /// Estimates the number of moves left to solve the puzzle without overestimating it.
+    ///
+    /// Returns `usize::MAX` if some goal can never be reached by an actor of its color.
     pub fn heuristic(&self, data: &Data) -> usize {
         let mut max_distance = 0;
 
-        for goal in data.goals.iter() {
+        for (goal_index, goal) in data.goals.iter().enumerate() {
             let mut min_distance = usize::MAX;
             for actor in self.actors.iter().filter(|a| a.color == goal.color) {
-                let d = (goal.position - actor.position).abs();
-                min_distance = usize::min(min_distance, (d.x + d.y) as usize);
+                min_distance =
+                    usize::min(min_distance, data.goal_distance(goal_index, actor.position));
             }
             max_distance = usize::max(max_distance, min_distance);
         }
@@ -179,45 +739,41 @@ impl State {
             .ok_or(ParseError::NoLineBreakAfterRows)?
             .0;
 
-        let mut tiles = vec![Tile::Impassable; size_x * size_y as usize];
+        let mut tiles = vec![Tile::Impassable; size_x * size_y];
         let mut goals = Vec::new();
-        let mut actors = ArrayVec::new();
+        let mut actors = Actors::new();
 
-        let mut lines = s.lines().enumerate();
+        let mut lines = s.lines().zip(1..);
         for y in (0..size_y).rev() {
-            let (line_number, line) = lines.next().unwrap();
+            let (line, line_number) = lines.next().unwrap();
 
             if line.len() != size_x {
                 return Err(ParseError::UnevenRows {
                     line_number,
+                    line: line.to_string(),
                     data_width: size_x,
                     line_width: line.len(),
                 });
             }
 
             for (x, c) in line.chars().enumerate() {
-                let tile = match c {
-                    '.' => Ok(Tile::Passable),
-                    ' ' => Ok(Tile::Impassable),
-                    'r' => {
-                        goals.push(Goal {
-                            position: Vec2::new(x as i32, y as i32),
-                            color: Color::Red,
-                        });
-                        Ok(Tile::Passable)
-                    }
-                    'b' => {
-                        goals.push(Goal {
-                            position: Vec2::new(x as i32, y as i32),
-                            color: Color::Blue,
-                        });
-                        Ok(Tile::Passable)
-                    }
-                    _ => Err(ParseError::UnexpectedCharacter {
-                        line_number,
-                        column_number: x + 1,
-                        character: c,
-                    }),
+                let tile = match Tile::from_char(c) {
+                    Some(tile) => Ok(tile),
+                    None => match Color::from_goal_char(c) {
+                        Some(color) => {
+                            goals.push(Goal {
+                                position: Vec2::new(x as i32, y as i32),
+                                color,
+                            });
+                            Ok(Tile::Passable)
+                        }
+                        None => Err(ParseError::UnexpectedCharacter {
+                            line_number,
+                            column_number: x + 1,
+                            line: line.to_string(),
+                            character: c,
+                        }),
+                    },
                 }?;
                 tiles[x + y * size_x] = tile;
             }
@@ -225,35 +781,48 @@ impl State {
 
         lines.next();
 
-        for (line_number, line) in lines {
+        for (line, line_number) in lines {
+            if line.is_empty() {
+                return Err(ParseError::EmptyActorDefinition { line_number });
+            }
+
+            let column_number = |piece: &str| piece.as_ptr() as usize - line.as_ptr() as usize + 1;
             let mut pieces = line.split(' ');
-            let color = match pieces
-                .next()
-                .ok_or(ParseError::EmptyActorDefinition { line_number })?
-            {
-                "R" => Color::Red,
-                "B" => Color::Blue,
-                c => {
+            let piece = pieces.next().unwrap();
+            let mut chars = piece.chars();
+            let color = match (chars.next().and_then(Color::from_actor_char), chars.next()) {
+                (Some(color), None) => color,
+                _ => {
                     return Err(ParseError::InvalidActorColor {
                         line_number,
-                        color: c.to_string(),
+                        column_number: 1,
+                        line: line.to_string(),
+                        color: piece.to_string(),
                     })
                 }
             };
-            let actor_x = pieces
-                .next()
-                .ok_or(ParseError::MissingActorX { line_number })?
+            let x_piece = pieces.next().ok_or_else(|| ParseError::MissingActorX {
+                line_number,
+                line: line.to_string(),
+            })?;
+            let actor_x = x_piece
                 .parse()
                 .map_err(|parse_error| ParseError::InvalidActorX {
                     line_number,
+                    column_number: column_number(x_piece),
+                    line: line.to_string(),
                     parse_error,
                 })?;
-            let actor_y = pieces
-                .next()
-                .ok_or(ParseError::MissingActorY { line_number })?
+            let y_piece = pieces.next().ok_or_else(|| ParseError::MissingActorY {
+                line_number,
+                line: line.to_string(),
+            })?;
+            let actor_y = y_piece
                 .parse()
                 .map_err(|parse_error| ParseError::InvalidActorY {
                     line_number,
+                    column_number: column_number(y_piece),
+                    line: line.to_string(),
                     parse_error,
                 })?;
 
@@ -265,56 +834,46 @@ impl State {
 
         Ok((
             State { actors },
-            Data {
-                size: Vec2::new(size_x as i32, size_y as i32),
-                tiles,
-                goals,
-            },
+            Data::new(Vec2::new(size_x as i32, size_y as i32), tiles, goals),
         ))
     }
 
-    pub fn display(&self, data: &Data, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let board_width = data.size.x + 2;
-        let board_height = data.size.y + 2;
-        let size = board_width * board_height;
-        let mut board = vec![' '; size as usize];
-
-        for y in 0..board_height {
-            for x in 0..board_width {
-                let index = x + y * board_width;
+    /// Writes the puzzle in the same format accepted by `State::parse`.
+    pub fn write_puzzle<W: fmt::Write>(&self, data: &Data, w: &mut W) -> fmt::Result {
+        for y in (0..data.size.y).rev() {
+            for x in 0..data.size.x {
                 let position = Vec2::new(x, y);
-                board[index as usize] = match data.tile(position) {
-                    Tile::Passable => '.',
-                    Tile::Impassable => ' ',
+                let c = match data.goals.iter().find(|g| g.position == position) {
+                    Some(goal) => goal.color.goal_char(),
+                    None => data.tile(position).to_char(),
                 };
+                w.write_char(c)?;
             }
+            writeln!(w)?;
         }
 
-        for goal in data.goals.iter() {
-            let index = goal.position.x + goal.position.y * board_width;
-            board[index as usize] = match goal.color {
-                Color::Red => 'r',
-                Color::Blue => 'b',
-            };
-        }
+        writeln!(w)?;
 
         for actor in self.actors.iter() {
-            let index = actor.position.x + actor.position.y * board_width;
-            board[index as usize] = match actor.color {
-                Color::Red => 'R',
-                Color::Blue => 'B',
-            };
-        }
-
-        for y in (0..board_height).rev() {
-            let begin = y * board_width;
-            let end = begin + board_width;
-            for c in &board[begin as usize..end as usize] {
-                write!(f, "{}", c)?;
-            }
-            writeln!(f)?;
+            writeln!(
+                w,
+                "{} {} {}",
+                actor.color.actor_char(),
+                actor.position.x,
+                actor.position.y
+            )?;
         }
 
         Ok(())
     }
+
+    pub fn to_puzzle_string(&self, data: &Data) -> String {
+        let mut result = String::new();
+        self.write_puzzle(data, &mut result).unwrap();
+        result
+    }
+
+    pub fn display(&self, data: &Data, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Renderer::new().render(self, data, f)
+    }
 }
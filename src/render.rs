@@ -0,0 +1,112 @@
+use crate::{Color, Data, State, Vec2};
+use std::fmt;
+
+/// Draws boards as text, framed by a border of impassable tiles.
+#[derive(Clone, Copy, Debug)]
+pub struct Renderer {
+    axes: bool,
+    highlight_goals: bool,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Renderer {
+    pub fn new() -> Self {
+        Self {
+            axes: false,
+            highlight_goals: true,
+        }
+    }
+
+    /// Prints x coordinates below the board and y coordinates to its left.
+    pub fn axes(mut self, axes: bool) -> Self {
+        self.axes = axes;
+        self
+    }
+
+    /// Draws actors standing on a goal of their own color with a distinct glyph.
+    pub fn highlight_goals(mut self, highlight_goals: bool) -> Self {
+        self.highlight_goals = highlight_goals;
+        self
+    }
+
+    /// The glyph for an actor standing on a goal of its own color.
+    #[inline]
+    pub fn solved_char(color: Color) -> char {
+        match color {
+            Color::Red => '*',
+            Color::Blue => '+',
+            Color::Green => '%',
+            Color::Yellow => '&',
+        }
+    }
+
+    fn glyph(&self, state: &State, data: &Data, position: Vec2) -> char {
+        let goal = data.goals().iter().find(|g| g.position() == position);
+        let actor = state.actors().iter().find(|a| a.position() == position);
+
+        match (actor, goal) {
+            (Some(actor), Some(goal)) if self.highlight_goals && actor.color() == goal.color() => {
+                Self::solved_char(actor.color())
+            }
+            (Some(actor), _) => actor.color().actor_char(),
+            (None, Some(goal)) => goal.color().goal_char(),
+            (None, None) => data.tile(position).to_char(),
+        }
+    }
+
+    pub fn render<W: fmt::Write>(&self, state: &State, data: &Data, w: &mut W) -> fmt::Result {
+        let size = data.size();
+        let label_width = if self.axes {
+            (size.y - 1).max(0).to_string().len() + 1
+        } else {
+            0
+        };
+        let border = "#".repeat(size.x as usize + 2);
+
+        writeln!(
+            w,
+            "{:label_width$}{}",
+            "",
+            border,
+            label_width = label_width
+        )?;
+        for y in (0..size.y).rev() {
+            if self.axes {
+                write!(w, "{:>width$} ", y, width = label_width - 1)?;
+            }
+            w.write_char('#')?;
+            for x in 0..size.x {
+                w.write_char(self.glyph(state, data, Vec2::new(x, y)))?;
+            }
+            writeln!(w, "#")?;
+        }
+        writeln!(
+            w,
+            "{:label_width$}{}",
+            "",
+            border,
+            label_width = label_width
+        )?;
+
+        if self.axes {
+            write!(w, "{:width$}", "", width = label_width + 1)?;
+            for x in 0..size.x {
+                write!(w, "{}", x % 10)?;
+            }
+            writeln!(w)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn to_string(&self, state: &State, data: &Data) -> String {
+        let mut result = String::new();
+        self.render(state, data, &mut result).unwrap();
+        result
+    }
+}
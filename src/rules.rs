@@ -0,0 +1,208 @@
+use crate::{Actor, Color, Data, Direction, State};
+use rustc_hash::FxHashMap;
+
+/// The mechanics of a puzzle: how actors move, what happens when they collide and when the puzzle
+/// is solved.
+///
+/// `StandardRules` implements the rules of the original game, apart from the swap handling it uses
+/// by default, see `SwapCollision`. The solvers assume that every move takes an actor along the
+/// tiles given by `Data::step` for the direction returned by `movement`, one step at a time while
+/// it slides over ice, and that it may stop anywhere on the way; rules that move actors otherwise
+/// or change the win condition should also override `heuristic` so it stays admissible.
+pub trait Rules {
+    /// Returns the direction an actor of the given color moves when the player moves in
+    /// `direction`.
+    fn movement(&self, color: Color, direction: Direction) -> Direction;
+
+    /// Resolves actors that collided while moving. `previous` holds the actors before the move and
+    /// `actors` the same actors, in the same order, after each one moved on its own. Actors sliding
+    /// over ice are resolved again after each further step, with `previous` holding the actors
+    /// before that step.
+    fn resolve_collisions(&self, previous: &[Actor], actors: &mut [Actor]);
+
+    #[inline]
+    fn is_solved(&self, state: &State, data: &Data) -> bool {
+        data.is_solved_by(state)
+    }
+
+    /// Estimates the number of moves left to solve the puzzle without overestimating it, or
+    /// returns `usize::MAX` if it can no longer be solved.
+    #[inline]
+    fn heuristic(&self, state: &State, data: &Data) -> usize {
+        state.heuristic(data)
+    }
+}
+
+/// What happens when two actors try to move through each other by swapping tiles.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SwapCollision {
+    /// The actors pass through each other and swap places.
+    #[default]
+    PassThrough,
+    /// Both actors stay where they were, like actors that move onto the same tile.
+    Revert,
+    /// The whole move is cancelled and no actor moves. Actors that swap while sliding over ice all
+    /// stop where they were before that step.
+    Block,
+}
+
+/// How actors that are blocked by other actors are found.
+///
+/// Both strategies give the same results and only differ in speed. `Chained` falls back to
+/// `Pairwise` when three or more actors move onto the same tile, since only the pairwise loop
+/// decides which of them get through.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Resolution {
+    /// Repeatedly compares every pair of actors and reverts the ones that collide until no
+    /// collisions are left. This takes quadratic time in the number of actors but is fast for the
+    /// handful of actors in most puzzles. When three or more actors move onto the same tile, they
+    /// are reverted in pairs, so the odd one out may still move onto it.
+    #[default]
+    Pairwise,
+    /// Follows each blocked actor to the actor moving onto its tile, so a train of actors stops as
+    /// soon as its lead is blocked. This takes linear time in the number of actors, except for
+    /// moves where three or more actors meet on one tile: those fall back to `Pairwise` and take
+    /// its quadratic time per pass. With several colors or teleporters sending actors to the same
+    /// tile this can happen often, so `Chained` only pays off for many actors moving in trains.
+    Chained,
+}
+
+/// The rules of the original game, extended with rotating colors. Each actor moves in the
+/// direction given by its color, see `Color`. Actors that would end up on the same tile stay where
+/// they were, which may in turn block other actors.
+///
+/// By default, actors that swap places pass through each other. This keeps the behaviour the solver
+/// has always had, while `SwapCollision::Revert` and `SwapCollision::Block` match the original
+/// game.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct StandardRules {
+    swaps: SwapCollision,
+    resolution: Resolution,
+}
+
+impl StandardRules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets what happens when two actors swap places.
+    pub fn swaps(mut self, swaps: SwapCollision) -> Self {
+        self.swaps = swaps;
+        self
+    }
+
+    /// Sets how actors blocked by other actors are found.
+    pub fn resolution(mut self, resolution: Resolution) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    fn resolve_pairwise(&self, previous: &[Actor], actors: &mut [Actor]) {
+        let swapped = |actors: &[Actor], i: usize, j: usize| {
+            actors[i].position() == previous[j].position()
+                && actors[j].position() == previous[i].position()
+        };
+
+        // Blocking depends only on where the actors tried to move, not on other collisions
+        if self.swaps == SwapCollision::Block
+            && (0..actors.len()).any(|i| (i + 1..actors.len()).any(|j| swapped(actors, i, j)))
+        {
+            actors.copy_from_slice(previous);
+            return;
+        }
+
+        let mut done = false;
+        while !done {
+            done = true;
+            for i in 0..actors.len() {
+                for j in i + 1..actors.len() {
+                    if actors[i].position() == actors[j].position()
+                        || (self.swaps == SwapCollision::Revert && swapped(actors, i, j))
+                    {
+                        actors[i] = previous[i];
+                        actors[j] = previous[j];
+                        done = false;
+                    }
+                }
+            }
+        }
+    }
+
+    fn resolve_chained(&self, previous: &[Actor], actors: &mut [Actor]) {
+        // The actor that starts on each tile, and the first actor moving onto it with their count
+        let occupants = (0..previous.len())
+            .map(|i| (previous[i].position(), i))
+            .collect::<FxHashMap<_, _>>();
+        let mut movers = FxHashMap::default();
+        for i in 0..actors.len() {
+            if actors[i].position() != previous[i].position() {
+                movers
+                    .entry(actors[i].position())
+                    .and_modify(|(_, count)| *count += 1)
+                    .or_insert((i, 1));
+            }
+        }
+
+        // The pairwise loop lets the odd one out of three or more actors onto a tile, depending on
+        // their order, which blocked chains can't follow
+        if movers.values().any(|&(_, count)| count > 2) {
+            self.resolve_pairwise(previous, actors);
+            return;
+        }
+
+        let swapped = |i: usize| {
+            occupants
+                .get(&actors[i].position())
+                .is_some_and(|&j| j != i && actors[j].position() == previous[i].position())
+        };
+        if self.swaps == SwapCollision::Block && (0..actors.len()).any(swapped) {
+            actors.copy_from_slice(previous);
+            return;
+        }
+
+        // Actors that stay on their starting tile, which blocks every actor moving onto it
+        let mut blocked = vec![false; actors.len()];
+        let mut stack = Vec::new();
+        for i in 0..actors.len() {
+            if actors[i].position() == previous[i].position()
+                || movers[&actors[i].position()].1 > 1
+                || (self.swaps == SwapCollision::Revert && swapped(i))
+            {
+                blocked[i] = true;
+                stack.push(i);
+            }
+        }
+        for &i in stack.iter() {
+            actors[i] = previous[i];
+        }
+
+        while let Some(j) = stack.pop() {
+            if let Some(&(i, 1)) = movers.get(&previous[j].position()) {
+                if !blocked[i] {
+                    blocked[i] = true;
+                    actors[i] = previous[i];
+                    stack.push(i);
+                }
+            }
+        }
+    }
+}
+
+impl Rules for StandardRules {
+    #[inline]
+    fn movement(&self, color: Color, direction: Direction) -> Direction {
+        match color {
+            Color::Red => direction,
+            Color::Blue => direction.reverse(),
+            Color::Green => direction.rotate_cw(),
+            Color::Yellow => direction.rotate_ccw(),
+        }
+    }
+
+    fn resolve_collisions(&self, previous: &[Actor], actors: &mut [Actor]) {
+        match self.resolution {
+            Resolution::Pairwise => self.resolve_pairwise(previous, actors),
+            Resolution::Chained => self.resolve_chained(previous, actors),
+        }
+    }
+}
@@ -1,26 +1,32 @@
-use crate::{Data, Direction, State, Transition};
+use crate::{Data, Direction, Packing, Rules, StandardRules, State, Transition};
 use rustc_hash::FxHasher;
 use std::{
     cmp::Ordering,
     collections::{hash_map::Entry, BinaryHeap, HashMap},
-    hash::BuildHasher,
+    fmt,
+    hash::{BuildHasher, Hash},
+    sync::{
+        atomic::{self, AtomicBool},
+        Arc,
+    },
+    time::Instant,
 };
 
 #[derive(Eq, PartialEq)]
-struct Node {
-    state: State,
+struct Node<T> {
+    state: T,
     distance: usize,
     estimate: usize,
     index: usize,
 }
 
-impl PartialOrd for Node {
+impl<T: Eq> PartialOrd for Node<T> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl Ord for Node {
+impl<T: Eq> Ord for Node<T> {
     fn cmp(&self, other: &Self) -> Ordering {
         other.estimate.cmp(&self.estimate)
     }
@@ -36,44 +42,279 @@ impl BuildHasher for FastHashBuilder {
     }
 }
 
-pub fn solve(initial_state: State, data: &Data) -> Option<Vec<Direction>> {
+/// A flag that can be raised from another thread to stop a running search.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, atomic::Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(atomic::Ordering::Relaxed)
+    }
+}
+
+/// The rules to solve a puzzle under, and limits on the work a search may do before giving up.
+#[derive(Clone, Debug)]
+pub struct SolverConfig<R = StandardRules> {
+    pub rules: R,
+    /// The maximum number of states to expand.
+    pub max_expanded: Option<usize>,
+    /// The point in time after which the search stops.
+    pub deadline: Option<Instant>,
+    /// The maximum number of states to keep in the visited set. Iterative-deepening A* only keeps
+    /// the current path in memory and ignores this limit.
+    pub max_states: Option<usize>,
+    /// A token checked before every expansion.
+    pub cancellation: Option<CancellationToken>,
+}
+
+impl<R> SolverConfig<R> {
+    /// Creates a config for the given rules without any limits.
+    pub fn with_rules(rules: R) -> Self {
+        Self {
+            rules,
+            max_expanded: None,
+            deadline: None,
+            max_states: None,
+            cancellation: None,
+        }
+    }
+
+    /// Returns why the search should stop, if it should. `states` is the size of the visited set,
+    /// or `None` for searches without one.
+    #[inline]
+    fn check(&self, statistics: &Statistics, states: Option<usize>) -> Option<AbortReason> {
+        if self
+            .max_expanded
+            .is_some_and(|max| statistics.expanded >= max)
+        {
+            Some(AbortReason::NodeLimit)
+        } else if self
+            .max_states
+            .zip(states)
+            .is_some_and(|(max, states)| states >= max)
+        {
+            Some(AbortReason::StateLimit)
+        } else if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            Some(AbortReason::Deadline)
+        } else if self.cancellation.as_ref().is_some_and(|c| c.is_cancelled()) {
+            Some(AbortReason::Cancelled)
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        Self::with_rules(StandardRules::new())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AbortReason {
+    NodeLimit,
+    StateLimit,
+    Deadline,
+    Cancelled,
+}
+
+impl fmt::Display for AbortReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            AbortReason::NodeLimit => write!(f, "expanded node limit reached"),
+            AbortReason::StateLimit => write!(f, "visited state limit reached"),
+            AbortReason::Deadline => write!(f, "time limit reached"),
+            AbortReason::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+/// Counters describing how much work a search did.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Statistics {
+    /// The number of states whose transitions were explored.
+    pub expanded: usize,
+    /// The number of successor states produced while expanding states.
+    pub generated: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SolveOutcome {
+    /// An optimal sequence of moves that solves the puzzle.
+    Solved(Vec<Direction>),
+    /// Every state reachable from the initial state was explored without finding a solution.
+    ///
+    /// `reachable_states` counts the distinct states explored, leaving out states from which some
+    /// goal can no longer be reached. Iterative-deepening A* does not remember the states it has
+    /// left, so it counts the states expanded in its final iteration instead, which may include a
+    /// state more than once.
+    Unsolvable { reachable_states: usize },
+    /// The search hit one of the limits in its `SolverConfig` before finishing.
+    Aborted { reason: AbortReason },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SolveResult {
+    pub outcome: SolveOutcome,
+    pub statistics: Statistics,
+}
+
+impl SolveResult {
+    /// Returns the solution if one was found.
+    pub fn solution(&self) -> Option<&[Direction]> {
+        match &self.outcome {
+            SolveOutcome::Solved(solution) => Some(solution),
+            _ => None,
+        }
+    }
+}
+
+pub fn solve(initial_state: State, data: &Data) -> SolveResult {
+    solve_with_config(initial_state, data, &SolverConfig::default())
+}
+
+/// What a hint suggests doing next.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Hint {
+    /// The first move of an optimal solution, which takes `distance` moves including this one.
+    Move {
+        direction: Direction,
+        distance: usize,
+    },
+    /// The puzzle is already solved.
+    Solved,
+    /// The puzzle cannot be solved from the state.
+    Unsolvable,
+    /// The search hit one of the limits in its `SolverConfig` before finishing.
+    Aborted { reason: AbortReason },
+}
+
+/// Suggests the first move of an optimal solution from `state`. Returns `None` if the puzzle is
+/// already solved or cannot be solved from `state`.
+pub fn hint(state: &State, data: &Data) -> Option<Direction> {
+    match hint_with_config(state, data, &SolverConfig::default()) {
+        Hint::Move { direction, .. } => Some(direction),
+        _ => None,
+    }
+}
+
+pub fn hint_with_config<R: Rules>(state: &State, data: &Data, config: &SolverConfig<R>) -> Hint {
+    if config.rules.is_solved(state, data) {
+        return Hint::Solved;
+    }
+    match solve_with_config(state.clone(), data, config).outcome {
+        SolveOutcome::Solved(solution) => Hint::Move {
+            direction: solution[0],
+            distance: solution.len(),
+        },
+        SolveOutcome::Unsolvable { .. } => Hint::Unsolvable,
+        SolveOutcome::Aborted { reason } => Hint::Aborted { reason },
+    }
+}
+
+pub fn solve_with_config<R: Rules>(
+    initial_state: State,
+    data: &Data,
+    config: &SolverConfig<R>,
+) -> SolveResult {
+    // Compact states are much cheaper to hash, compare and store, so use them whenever they fit
+    match Packing::new(&initial_state, data) {
+        Some(packing) => solve_encoded(
+            initial_state,
+            data,
+            config,
+            |state| packing.pack(&state),
+            |&state| packing.unpack(state),
+        ),
+        None => solve_encoded(initial_state, data, config, |state| state, State::clone),
+    }
+}
+
+/// Runs A*, storing states in the visited set and queue as the keys produced by `encode`.
+fn solve_encoded<R: Rules, K: Hash + Eq>(
+    initial_state: State,
+    data: &Data,
+    config: &SolverConfig<R>,
+    encode: impl Fn(State) -> K,
+    decode: impl Fn(&K) -> State,
+) -> SolveResult {
+    let mut statistics = Statistics::default();
     let mut states = HashMap::with_capacity_and_hasher(4 * 1024, FastHashBuilder);
     let mut parents = Vec::with_capacity(4 * 1024);
     let mut queue = BinaryHeap::with_capacity(1024);
 
     // Insert initial state
-    let initial_transitions = initial_state.transitions(data);
-    states.insert(initial_state, ());
+    let initial_transitions = initial_state.transitions_with(data, &config.rules);
+    states.insert(encode(initial_state), ());
 
     // Add transitions from initial state
+    statistics.expanded += 1;
     for (action, transition) in initial_transitions {
+        statistics.generated += 1;
         match transition {
             Transition::Indeterminate(state) => {
+                let heuristic = config.rules.heuristic(&state, data);
+                if heuristic == usize::MAX {
+                    continue;
+                }
+
                 parents.push((0, action));
 
-                let estimate = state.heuristic(data) + 1;
+                let estimate = heuristic + 1;
                 queue.push(Node {
-                    state,
+                    state: encode(state),
                     distance: 1,
                     estimate,
                     index: parents.len(),
                 });
             }
-            Transition::Success => return Some(vec![action]),
+            Transition::Success => {
+                return SolveResult {
+                    outcome: SolveOutcome::Solved(vec![action]),
+                    statistics,
+                }
+            }
         }
     }
 
     // Pop states in order
     while let Some(parent_node) = queue.pop() {
+        if let Some(reason) = config.check(&statistics, Some(states.len())) {
+            return SolveResult {
+                outcome: SolveOutcome::Aborted { reason },
+                statistics,
+            };
+        }
+
         if let Entry::Vacant(entry) = states.entry(parent_node.state) {
-            for (action, transition) in entry.key().transitions(data) {
+            statistics.expanded += 1;
+            for (action, transition) in decode(entry.key()).transitions_with(data, &config.rules) {
+                statistics.generated += 1;
                 match transition {
                     Transition::Indeterminate(state) => {
+                        // Some goal can no longer be reached from this state
+                        let heuristic = config.rules.heuristic(&state, data);
+                        if heuristic == usize::MAX {
+                            continue;
+                        }
+
                         parents.push((parent_node.index, action));
 
-                        let estimate = state.heuristic(data) + (parent_node.distance + 1);
+                        let estimate = heuristic + (parent_node.distance + 1);
                         queue.push(Node {
-                            state: state,
+                            state: encode(state),
                             distance: parent_node.distance + 1,
                             estimate,
                             index: parents.len(),
@@ -88,8 +329,10 @@ pub fn solve(initial_state: State, data: &Data) -> Option<Vec<Direction>> {
                             current_index = next_index;
                         }
                         result_actions.reverse();
-                        // println!("Explored {} states", parents.len());
-                        return Some(result_actions);
+                        return SolveResult {
+                            outcome: SolveOutcome::Solved(result_actions),
+                            statistics,
+                        };
                     }
                 }
             }
@@ -97,5 +340,357 @@ pub fn solve(initial_state: State, data: &Data) -> Option<Vec<Direction>> {
         }
     }
 
-    None
+    SolveResult {
+        outcome: SolveOutcome::Unsolvable {
+            reachable_states: states.len(),
+        },
+        statistics,
+    }
+}
+
+enum Search {
+    Found,
+    Exceeded(usize),
+    Exhausted,
+    Aborted(AbortReason),
+}
+
+fn search<R: Rules>(
+    path: &mut Vec<State>,
+    actions: &mut Vec<Direction>,
+    data: &Data,
+    bound: usize,
+    config: &SolverConfig<R>,
+    statistics: &mut Statistics,
+) -> Search {
+    if let Some(reason) = config.check(statistics, None) {
+        return Search::Aborted(reason);
+    }
+
+    let distance = actions.len() + 1;
+    let mut next_bound = None;
+
+    statistics.expanded += 1;
+    for (action, transition) in path.last().unwrap().transitions_with(data, &config.rules) {
+        statistics.generated += 1;
+        match transition {
+            Transition::Indeterminate(state) => {
+                // Skip states already on the current path, including moves that change nothing
+                if path.contains(&state) {
+                    continue;
+                }
+
+                let heuristic = config.rules.heuristic(&state, data);
+                if heuristic == usize::MAX {
+                    continue;
+                }
+
+                let estimate = heuristic + distance;
+                if estimate > bound {
+                    next_bound = Some(next_bound.map_or(estimate, |b| usize::min(b, estimate)));
+                    continue;
+                }
+
+                path.push(state);
+                actions.push(action);
+                match search(path, actions, data, bound, config, statistics) {
+                    Search::Found => return Search::Found,
+                    Search::Aborted(reason) => return Search::Aborted(reason),
+                    Search::Exceeded(estimate) => {
+                        next_bound = Some(next_bound.map_or(estimate, |b| usize::min(b, estimate)));
+                    }
+                    Search::Exhausted => (),
+                }
+                actions.pop();
+                path.pop();
+            }
+            Transition::Success => {
+                if distance > bound {
+                    next_bound = Some(next_bound.map_or(distance, |b| usize::min(b, distance)));
+                } else {
+                    actions.push(action);
+                    return Search::Found;
+                }
+            }
+        }
+    }
+
+    match next_bound {
+        Some(bound) => Search::Exceeded(bound),
+        None => Search::Exhausted,
+    }
+}
+
+/// Solves the puzzle with iterative-deepening A*.
+///
+/// Only the current path is kept in memory, so this uses memory proportional to the solution
+/// depth at the cost of re-expanding states on every iteration.
+pub fn solve_ida(initial_state: State, data: &Data) -> SolveResult {
+    solve_ida_with_config(initial_state, data, &SolverConfig::default())
+}
+
+pub fn solve_ida_with_config<R: Rules>(
+    initial_state: State,
+    data: &Data,
+    config: &SolverConfig<R>,
+) -> SolveResult {
+    let mut statistics = Statistics::default();
+    let mut bound = config.rules.heuristic(&initial_state, data);
+    let mut path = vec![initial_state];
+    let mut actions = Vec::new();
+
+    let outcome = loop {
+        let expanded = statistics.expanded;
+        match search(
+            &mut path,
+            &mut actions,
+            data,
+            bound,
+            config,
+            &mut statistics,
+        ) {
+            Search::Found => break SolveOutcome::Solved(actions),
+            Search::Exceeded(next_bound) => bound = next_bound,
+            Search::Exhausted => {
+                break SolveOutcome::Unsolvable {
+                    reachable_states: statistics.expanded - expanded,
+                }
+            }
+            Search::Aborted(reason) => break SolveOutcome::Aborted { reason },
+        }
+    };
+
+    SolveResult {
+        outcome,
+        statistics,
+    }
+}
+
+/// Every optimal solution to a puzzle.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OptimalSolutions {
+    /// Distinct optimal solutions, at most as many as the requested limit.
+    pub solutions: Vec<Vec<Direction>>,
+    /// The total number of distinct optimal solutions, saturating at `usize::MAX`.
+    pub count: usize,
+    /// The number of moves in each optimal solution.
+    pub length: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SolveAllOutcome {
+    /// The optimal solutions that solve the puzzle.
+    Solved(OptimalSolutions),
+    /// Every state reachable from the initial state was explored without finding a solution.
+    /// `reachable_states` counts them as in `SolveOutcome::Unsolvable`.
+    Unsolvable { reachable_states: usize },
+    /// The search hit one of the limits in its `SolverConfig` before finishing.
+    Aborted { reason: AbortReason },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SolveAllResult {
+    pub outcome: SolveAllOutcome,
+    pub statistics: Statistics,
+}
+
+impl SolveAllResult {
+    /// Returns the optimal solutions if any were found.
+    pub fn optimal(&self) -> Option<&OptimalSolutions> {
+        match &self.outcome {
+            SolveAllOutcome::Solved(optimal) => Some(optimal),
+            _ => None,
+        }
+    }
+}
+
+struct Record {
+    distance: usize,
+    parents: Vec<(usize, Direction)>,
+    expanded: bool,
+}
+
+/// Finds every distinct shortest sequence of moves that solves the puzzle, returning at most
+/// `limit` of them along with the total count.
+pub fn solve_all(initial_state: State, data: &Data, limit: usize) -> SolveAllResult {
+    solve_all_with_config(initial_state, data, &SolverConfig::default(), limit)
+}
+
+pub fn solve_all_with_config<R: Rules>(
+    initial_state: State,
+    data: &Data,
+    config: &SolverConfig<R>,
+    limit: usize,
+) -> SolveAllResult {
+    let rules = &config.rules;
+    let mut statistics = Statistics::default();
+
+    let initial_estimate = rules.heuristic(&initial_state, data);
+    if initial_estimate == usize::MAX {
+        return SolveAllResult {
+            outcome: SolveAllOutcome::Unsolvable {
+                reachable_states: 1,
+            },
+            statistics,
+        };
+    }
+
+    let mut states = HashMap::with_capacity_and_hasher(4 * 1024, FastHashBuilder);
+    let mut records = Vec::with_capacity(4 * 1024);
+    let mut queue = BinaryHeap::with_capacity(1024);
+    let mut solution_length = None;
+    let mut goal_parents = Vec::new();
+
+    // Unlike `solve`, every parent at the optimal distance is kept for each state
+    states.insert(initial_state.clone(), 0);
+    records.push(Record {
+        distance: 0,
+        parents: Vec::new(),
+        expanded: false,
+    });
+    queue.push(Node {
+        state: initial_state,
+        distance: 0,
+        estimate: initial_estimate,
+        index: 0,
+    });
+
+    while let Some(parent_node) = queue.pop() {
+        // Once a solution is known, only states that could still be on an optimal path matter
+        if solution_length.is_some_and(|length| parent_node.estimate > length) {
+            break;
+        }
+
+        if let Some(reason) = config.check(&statistics, Some(states.len())) {
+            return SolveAllResult {
+                outcome: SolveAllOutcome::Aborted { reason },
+                statistics,
+            };
+        }
+
+        let parent = &mut records[parent_node.index];
+        if parent.expanded || parent.distance != parent_node.distance {
+            continue;
+        }
+        parent.expanded = true;
+
+        statistics.expanded += 1;
+        let distance = parent_node.distance + 1;
+        for (action, transition) in parent_node.state.transitions_with(data, rules) {
+            statistics.generated += 1;
+            match transition {
+                Transition::Indeterminate(state) => {
+                    let heuristic = rules.heuristic(&state, data);
+                    if heuristic == usize::MAX {
+                        continue;
+                    }
+
+                    let index = match states.get(&state) {
+                        Some(&index) => {
+                            let record = &mut records[index];
+                            if distance >= record.distance {
+                                if distance == record.distance {
+                                    record.parents.push((parent_node.index, action));
+                                }
+                                continue;
+                            }
+                            record.distance = distance;
+                            record.parents.clear();
+                            record.parents.push((parent_node.index, action));
+                            index
+                        }
+                        None => {
+                            states.insert(state.clone(), records.len());
+                            records.push(Record {
+                                distance,
+                                parents: vec![(parent_node.index, action)],
+                                expanded: false,
+                            });
+                            records.len() - 1
+                        }
+                    };
+
+                    queue.push(Node {
+                        state,
+                        distance,
+                        estimate: heuristic + distance,
+                        index,
+                    });
+                }
+                Transition::Success => match solution_length {
+                    Some(length) if length < distance => (),
+                    Some(length) if length == distance => {
+                        goal_parents.push((parent_node.index, action))
+                    }
+                    _ => {
+                        solution_length = Some(distance);
+                        goal_parents.clear();
+                        goal_parents.push((parent_node.index, action));
+                    }
+                },
+            }
+        }
+    }
+
+    let length = match solution_length {
+        Some(length) => length,
+        None => {
+            return SolveAllResult {
+                outcome: SolveAllOutcome::Unsolvable {
+                    reachable_states: states.len(),
+                },
+                statistics,
+            }
+        }
+    };
+
+    // Count the optimal paths reaching each state, in order of increasing distance
+    let mut order = (0..records.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&index| records[index].distance);
+    let mut path_counts = vec![0usize; records.len()];
+    path_counts[0] = 1;
+    for index in order.into_iter().skip(1) {
+        path_counts[index] = records[index]
+            .parents
+            .iter()
+            .fold(0, |count: usize, &(parent, _)| {
+                count.saturating_add(path_counts[parent])
+            });
+    }
+    let count = goal_parents.iter().fold(0, |count: usize, &(parent, _)| {
+        count.saturating_add(path_counts[parent])
+    });
+
+    // Walk backwards from each goal, choosing parents depth-first
+    let mut solutions = Vec::new();
+    let mut stack = goal_parents
+        .iter()
+        .rev()
+        .map(|&(index, action)| (index, vec![action]))
+        .collect::<Vec<_>>();
+    while let Some((index, mut actions)) = stack.pop() {
+        if solutions.len() >= limit {
+            break;
+        }
+
+        if index == 0 {
+            actions.reverse();
+            solutions.push(actions);
+        } else {
+            for &(parent, action) in records[index].parents.iter().rev() {
+                let mut parent_actions = actions.clone();
+                parent_actions.push(action);
+                stack.push((parent, parent_actions));
+            }
+        }
+    }
+
+    SolveAllResult {
+        outcome: SolveAllOutcome::Solved(OptimalSolutions {
+            solutions,
+            count,
+            length,
+        }),
+        statistics,
+    }
 }
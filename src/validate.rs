@@ -0,0 +1,233 @@
+use crate::{Color, Data, State, Tile, Vec2};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    /// The puzzle is malformed or cannot be solved.
+    Error,
+    /// The puzzle is solvable but probably not what the author intended.
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Diagnostic {
+    ActorOutOfBounds {
+        position: Vec2,
+    },
+    ActorOnImpassableTile {
+        position: Vec2,
+    },
+    OverlappingActors {
+        position: Vec2,
+    },
+    GoalOutOfBounds {
+        position: Vec2,
+    },
+    GoalOnImpassableTile {
+        position: Vec2,
+    },
+    GoalOnSpecialTile {
+        position: Vec2,
+        tile: Tile,
+    },
+    OverlappingGoals {
+        position: Vec2,
+    },
+    NotEnoughActors {
+        color: Color,
+        goals: usize,
+        actors: usize,
+    },
+    UnreachableGoal {
+        position: Vec2,
+        color: Color,
+    },
+    InvalidTeleporter {
+        position: Vec2,
+        number: u8,
+    },
+    UnpairedTeleporter {
+        position: Vec2,
+        number: u8,
+    },
+    NoGoals,
+    AlreadySolved,
+}
+
+impl Diagnostic {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Diagnostic::UnpairedTeleporter { .. }
+            | Diagnostic::NoGoals
+            | Diagnostic::AlreadySolved => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnostic::ActorOutOfBounds { position } => {
+                write!(
+                    f,
+                    "actor at ({}, {}) is outside the board",
+                    position.x, position.y
+                )
+            }
+            Diagnostic::ActorOnImpassableTile { position } => write!(
+                f,
+                "actor at ({}, {}) is on an impassable tile",
+                position.x, position.y
+            ),
+            Diagnostic::OverlappingActors { position } => {
+                write!(f, "more than one actor at ({}, {})", position.x, position.y)
+            }
+            Diagnostic::GoalOutOfBounds { position } => {
+                write!(
+                    f,
+                    "goal at ({}, {}) is outside the board",
+                    position.x, position.y
+                )
+            }
+            Diagnostic::GoalOnImpassableTile { position } => write!(
+                f,
+                "goal at ({}, {}) is on an impassable tile",
+                position.x, position.y
+            ),
+            Diagnostic::GoalOnSpecialTile { position, tile } => write!(
+                f,
+                "goal at ({}, {}) is on a '{}' tile, but goals can only be saved on passable tiles",
+                position.x,
+                position.y,
+                tile.to_char()
+            ),
+            Diagnostic::OverlappingGoals { position } => {
+                write!(f, "more than one goal at ({}, {})", position.x, position.y)
+            }
+            Diagnostic::NotEnoughActors {
+                color,
+                goals,
+                actors,
+            } => write!(
+                f,
+                "{} {:?} goals but only {} {:?} actors",
+                goals, color, actors, color
+            ),
+            Diagnostic::UnreachableGoal { position, color } => write!(
+                f,
+                "no {:?} actor can reach the goal at ({}, {})",
+                color, position.x, position.y
+            ),
+            Diagnostic::InvalidTeleporter { position, number } => write!(
+                f,
+                "teleporter at ({}, {}) has number {}, but teleporters are numbered from 0 to 9",
+                position.x, position.y, number
+            ),
+            Diagnostic::UnpairedTeleporter { position, number } => write!(
+                f,
+                "teleporter {} at ({}, {}) does not have exactly one partner",
+                number, position.x, position.y
+            ),
+            Diagnostic::NoGoals => write!(f, "the puzzle has no goals"),
+            Diagnostic::AlreadySolved => write!(f, "the puzzle is solved before any moves"),
+        }
+    }
+}
+
+/// Checks a puzzle for mistakes that the parser accepts, such as actors on walls or goals that
+/// can never be reached.
+pub fn validate(state: &State, data: &Data) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let actors = state.actors();
+    let goals = data.goals();
+
+    for (i, actor) in actors.iter().enumerate() {
+        let position = actor.position();
+        if data.index(position).is_none() {
+            diagnostics.push(Diagnostic::ActorOutOfBounds { position });
+        } else if data.tile(position) == Tile::Impassable {
+            diagnostics.push(Diagnostic::ActorOnImpassableTile { position });
+        }
+        if actors[..i].iter().any(|a| a.position() == position) {
+            diagnostics.push(Diagnostic::OverlappingActors { position });
+        }
+    }
+
+    for (i, goal) in goals.iter().enumerate() {
+        let position = goal.position();
+        if data.index(position).is_none() {
+            diagnostics.push(Diagnostic::GoalOutOfBounds { position });
+        } else if data.tile(position) == Tile::Impassable {
+            diagnostics.push(Diagnostic::GoalOnImpassableTile { position });
+        } else if data.tile(position) != Tile::Passable {
+            diagnostics.push(Diagnostic::GoalOnSpecialTile {
+                position,
+                tile: data.tile(position),
+            });
+        }
+        if goals[..i].iter().any(|g| g.position() == position) {
+            diagnostics.push(Diagnostic::OverlappingGoals { position });
+        }
+    }
+
+    for color in Color::ALL {
+        let goal_count = goals.iter().filter(|g| g.color() == color).count();
+        let actor_count = actors.iter().filter(|a| a.color() == color).count();
+        if goal_count > actor_count {
+            diagnostics.push(Diagnostic::NotEnoughActors {
+                color,
+                goals: goal_count,
+                actors: actor_count,
+            });
+        }
+    }
+
+    for (goal_index, goal) in goals.iter().enumerate() {
+        let reachable = actors
+            .iter()
+            .filter(|a| a.color() == goal.color() && data.index(a.position()).is_some())
+            .any(|a| data.goal_distance(goal_index, a.position()) != usize::MAX);
+        if !reachable && actors.iter().any(|a| a.color() == goal.color()) {
+            diagnostics.push(Diagnostic::UnreachableGoal {
+                position: goal.position(),
+                color: goal.color(),
+            });
+        }
+    }
+
+    let size = data.size();
+    for (index, tile) in data.tiles().iter().enumerate() {
+        if let Tile::Teleporter(number) = *tile {
+            let index = index as i32;
+            let position = Vec2::new(index % size.x, index / size.x);
+            let count = data
+                .tiles()
+                .iter()
+                .filter(|&&t| t == Tile::Teleporter(number))
+                .count();
+            if number > 9 {
+                diagnostics.push(Diagnostic::InvalidTeleporter { position, number });
+            } else if count != 2 {
+                diagnostics.push(Diagnostic::UnpairedTeleporter { position, number });
+            }
+        }
+    }
+
+    if goals.is_empty() {
+        diagnostics.push(Diagnostic::NoGoals);
+    } else if data.is_solved_by(state) {
+        diagnostics.push(Diagnostic::AlreadySolved);
+    }
+
+    diagnostics
+}
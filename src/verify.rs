@@ -0,0 +1,41 @@
+use crate::{Data, Direction, Rules, StandardRules, State};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VerifyResult {
+    /// The puzzle was first solved after `step` moves, and `extra_moves` more moves followed.
+    Solved { step: usize, extra_moves: usize },
+    /// The puzzle was not solved at any point while replaying the moves.
+    Incomplete,
+}
+
+/// Replays `moves` from `initial_state` and reports whether and when they solve the puzzle.
+pub fn verify(initial_state: &State, data: &Data, moves: &[Direction]) -> VerifyResult {
+    verify_with_rules(initial_state, data, &StandardRules::new(), moves)
+}
+
+pub fn verify_with_rules<R: Rules>(
+    initial_state: &State,
+    data: &Data,
+    rules: &R,
+    moves: &[Direction],
+) -> VerifyResult {
+    let mut state = initial_state.clone();
+    for (step, &direction) in moves.iter().enumerate() {
+        if rules.is_solved(&state, data) {
+            return VerifyResult::Solved {
+                step,
+                extra_moves: moves.len() - step,
+            };
+        }
+        state = state.transition_with(data, rules, direction);
+    }
+
+    if rules.is_solved(&state, data) {
+        VerifyResult::Solved {
+            step: moves.len(),
+            extra_moves: 0,
+        }
+    } else {
+        VerifyResult::Incomplete
+    }
+}
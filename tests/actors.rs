@@ -0,0 +1,16 @@
+use anima_solver::*;
+
+#[test]
+fn parse_and_solve_more_actors_than_fit_inline() {
+    let mut source = String::from(".rrrrrrrrr\n\n");
+    for x in 0..9 {
+        source.push_str(&format!("R {} 0\n", x));
+    }
+    let (state, data) = State::parse(&source).unwrap();
+
+    assert_eq!(state.actors().len(), 9);
+    assert_eq!(
+        solve(state, &data).solution(),
+        Some(&[Direction::Right][..])
+    );
+}
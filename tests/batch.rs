@@ -0,0 +1,23 @@
+use std::process::Command;
+
+#[test]
+fn directories_are_solved_in_order_with_summary() {
+    let output = Command::new(env!("CARGO_BIN_EXE_anima_solver"))
+        .args(["-q", "-j", "4"])
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/puzzles"))
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    let headers = stdout
+        .lines()
+        .filter(|line| line.ends_with(".txt:"))
+        .collect::<Vec<_>>();
+    let mut sorted = headers.clone();
+    sorted.sort();
+    assert_eq!(headers.len(), 23);
+    assert_eq!(headers, sorted);
+
+    assert!(stdout.contains("Summary:\n  Solved      23\n"));
+    assert!(stdout.contains("  Errors      0\n  Total       23\n"));
+}
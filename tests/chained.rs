@@ -0,0 +1,131 @@
+use anima_solver::*;
+use std::{fs, path::Path};
+
+fn rules(swaps: SwapCollision, resolution: Resolution) -> StandardRules {
+    StandardRules::new().swaps(swaps).resolution(resolution)
+}
+
+/// A single row of red actors, with a wall at the right end if `walled`.
+fn train(length: i32, walled: bool) -> (State, Data) {
+    let mut builder = PuzzleBuilder::new(Vec2::new(length + 1, 1));
+    if walled {
+        builder
+            .tile(Vec2::new(length, 0), Tile::Impassable)
+            .goal(Vec2::new(0, 0), Color::Red);
+    } else {
+        builder.goal(Vec2::new(length, 0), Color::Red);
+    }
+    for x in 0..length {
+        builder.actor(Vec2::new(x, 0), Color::Red);
+    }
+    builder.build().unwrap()
+}
+
+#[test]
+fn train_moves_behind_free_lead() {
+    let (state, data) = train(1000, false);
+    let chained = rules(SwapCollision::PassThrough, Resolution::Chained);
+
+    let moved = state.transition_with(&data, &chained, Direction::Right);
+    assert!(moved.actors().iter().all(|a| a.position().x > 0));
+    assert!(data.is_solved_by(&moved));
+}
+
+#[test]
+fn train_stops_behind_blocked_lead() {
+    let (state, data) = train(1000, true);
+    let chained = rules(SwapCollision::PassThrough, Resolution::Chained);
+
+    assert_eq!(
+        state.transition_with(&data, &chained, Direction::Right),
+        state
+    );
+}
+
+#[test]
+fn chained_matches_pairwise_on_random_states() {
+    // A small linear congruential generator keeps the test deterministic
+    let mut seed = 0x2545_f491_4f6c_dd1du64;
+    let mut next = |bound: u64| {
+        seed = seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (seed >> 33) % bound
+    };
+
+    for _ in 0..500 {
+        let mut builder = PuzzleBuilder::new(Vec2::new(5, 5));
+        builder.goal(Vec2::new(0, 0), Color::Red);
+        for y in 0..5 {
+            for x in 0..5 {
+                match next(4) {
+                    0 => {
+                        builder.tile(Vec2::new(x, y), Tile::Impassable);
+                    }
+                    1 => {
+                        builder.actor(Vec2::new(x, y), Color::ALL[next(4) as usize]);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        let (state, data) = match builder.build() {
+            Ok(puzzle) => puzzle,
+            Err(_) => continue,
+        };
+
+        for swaps in [
+            SwapCollision::PassThrough,
+            SwapCollision::Revert,
+            SwapCollision::Block,
+        ] {
+            for direction in Direction::ALL {
+                assert_eq!(
+                    state.transition_with(&data, &rules(swaps, Resolution::Chained), direction),
+                    state.transition_with(&data, &rules(swaps, Resolution::Pairwise), direction),
+                    "{:?} {:?}\n{}",
+                    swaps,
+                    direction,
+                    state.to_puzzle_string(&data)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn chained_solves_bundled_puzzles_optimally() {
+    let puzzles = Path::new(env!("CARGO_MANIFEST_DIR")).join("puzzles");
+    let manifest =
+        Manifest::parse(&fs::read_to_string(puzzles.join("expected.toml")).unwrap()).unwrap();
+    let config = SolverConfig::with_rules(rules(SwapCollision::PassThrough, Resolution::Chained));
+
+    for (name, expected) in manifest.entries() {
+        let source = fs::read_to_string(puzzles.join(name)).unwrap();
+        let (state, data) = State::parse(&source).unwrap();
+        let result = solve_with_config(state, &data, &config);
+        assert_eq!(
+            result.solution().map(<[_]>::len),
+            Some(*expected),
+            "{}",
+            name
+        );
+    }
+}
+
+#[test]
+fn three_way_collision_matches_pairwise() {
+    // Red and green meet first and are reverted, which lets blue through
+    let (state, data) = State::parse("...\n..r\n\nR 0 0\nG 1 1\nB 2 0\n").unwrap();
+    let (expected, _) = State::parse("...\n..r\n\nR 0 0\nB 1 0\nG 1 1\n").unwrap();
+
+    for resolution in [Resolution::Pairwise, Resolution::Chained] {
+        let rules = rules(SwapCollision::PassThrough, resolution);
+        assert_eq!(
+            state.transition_with(&data, &rules, Direction::Right),
+            expected,
+            "{:?}",
+            resolution
+        );
+    }
+}
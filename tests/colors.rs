@@ -0,0 +1,42 @@
+use anima_solver::*;
+
+const ROTATING: &str = "..g\n.. \ny..\n\nG 0 2\nY 2 0\n";
+
+#[test]
+fn rotating_colors_move_sideways() {
+    let (state, data) = State::parse(ROTATING).unwrap();
+
+    let moved = state.transition(&data, Direction::Up);
+    let mut positions = moved
+        .actors()
+        .iter()
+        .map(|a| (a.color(), a.position()))
+        .collect::<Vec<_>>();
+    positions.sort();
+    assert_eq!(
+        positions,
+        [
+            (Color::Green, Vec2::new(1, 2)),
+            (Color::Yellow, Vec2::new(1, 0))
+        ]
+    );
+
+    assert_eq!(
+        solve(state, &data).solution(),
+        Some(&[Direction::Up, Direction::Up][..])
+    );
+}
+
+#[test]
+fn rotating_colors_round_trip_and_render() {
+    let (state, data) = State::parse(ROTATING).unwrap();
+    assert_eq!(state.to_puzzle_string(&data), ROTATING);
+
+    let solved = state
+        .transition(&data, Direction::Up)
+        .transition(&data, Direction::Up);
+    assert_eq!(
+        Renderer::new().to_string(&solved, &data),
+        "#####\n#..%#\n#.. #\n#&..#\n#####\n"
+    );
+}
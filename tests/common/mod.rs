@@ -0,0 +1,26 @@
+// Each test crate includes this module but uses only some of its helpers
+#![allow(dead_code)]
+
+use anima_solver::{Data, State};
+use std::{fs, path::PathBuf};
+
+/// Lists the puzzle files bundled in `puzzles/`, sorted by name.
+pub fn bundled_puzzles() -> Vec<PathBuf> {
+    let puzzles = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("puzzles");
+
+    let mut result = fs::read_dir(puzzles)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|e| e == "txt"))
+        .collect::<Vec<_>>();
+    result.sort();
+    result
+}
+
+/// Parses the bundled puzzle with the given file name.
+pub fn load(name: &str) -> (State, Data) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("puzzles")
+        .join(name);
+    State::parse(&fs::read_to_string(path).unwrap()).unwrap()
+}
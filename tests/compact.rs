@@ -0,0 +1,41 @@
+mod common;
+
+use anima_solver::*;
+use std::fs;
+
+#[test]
+fn bundled_states_round_trip_through_packing() {
+    for path in common::bundled_puzzles() {
+        let (initial_state, data) = State::parse(&fs::read_to_string(&path).unwrap()).unwrap();
+        let packing = Packing::new(&initial_state, &data).unwrap();
+
+        let mut sorted = initial_state.actors().to_vec();
+        sorted.sort();
+        let unpacked = packing.unpack(packing.pack(&initial_state));
+        assert_eq!(unpacked.actors(), sorted, "{}", path.display());
+
+        for direction in Direction::ALL {
+            let state = initial_state.transition(&data, direction);
+            assert_eq!(
+                packing.unpack(packing.pack(&state)),
+                state,
+                "{}",
+                path.display()
+            );
+        }
+    }
+}
+
+#[test]
+fn large_puzzles_fall_back_to_unpacked_states() {
+    // 14 bits per actor on a 100x100 board, so ten actors need more than 128 bits
+    let mut builder = PuzzleBuilder::new(Vec2::new(100, 100));
+    for x in 0..10 {
+        builder.actor(Vec2::new(x, 0), Color::Red);
+    }
+    builder.goal(Vec2::new(9, 1), Color::Red);
+    let (state, data) = builder.build().unwrap();
+
+    assert!(Packing::new(&state, &data).is_none());
+    assert_eq!(solve(state, &data).solution(), Some(&[Direction::Up][..]));
+}
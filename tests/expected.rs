@@ -0,0 +1,91 @@
+mod common;
+
+use anima_solver::*;
+use std::{fs, path::Path, process::Command};
+
+#[test]
+fn bundled_puzzles_match_expected_lengths() {
+    let puzzles = Path::new(env!("CARGO_MANIFEST_DIR")).join("puzzles");
+    let manifest =
+        Manifest::parse(&fs::read_to_string(puzzles.join("expected.toml")).unwrap()).unwrap();
+
+    let mut count = 0;
+    for path in common::bundled_puzzles() {
+        let name = path.file_name().unwrap().to_str().unwrap();
+        let expected = manifest
+            .get(name)
+            .unwrap_or_else(|| panic!("{} is missing from expected.toml", name));
+
+        let (state, data) = State::parse(&fs::read_to_string(&path).unwrap()).unwrap();
+        let result = solve(state.clone(), &data);
+        let solution = result
+            .solution()
+            .unwrap_or_else(|| panic!("{} was not solved", name));
+
+        assert_eq!(solution.len(), expected, "{}", name);
+        assert_eq!(
+            verify(&state, &data, solution),
+            VerifyResult::Solved {
+                step: expected,
+                extra_moves: 0
+            },
+            "{}",
+            name
+        );
+        count += 1;
+    }
+
+    assert_eq!(count, manifest.entries().len());
+}
+
+#[test]
+fn parse_manifest() {
+    let manifest = Manifest::parse(
+        "# comment\n\n\"1.1-line_dance.txt\" = 2\nbare_name = 16 # trailing comment\n",
+    )
+    .unwrap();
+    assert_eq!(
+        manifest.entries(),
+        [
+            ("1.1-line_dance.txt".to_string(), 2),
+            ("bare_name".to_string(), 16)
+        ]
+    );
+
+    assert!(matches!(
+        Manifest::parse("a.b = 2"),
+        Err(ManifestError::ExpectedEquals { line_number: 1, .. })
+    ));
+    assert!(matches!(
+        Manifest::parse("a = two"),
+        Err(ManifestError::InvalidLength { line_number: 1, .. })
+    ));
+    assert!(matches!(
+        Manifest::parse("a = 1\na = 2"),
+        Err(ManifestError::DuplicateName { line_number: 2, .. })
+    ));
+}
+
+#[test]
+fn check_reports_mismatched_lengths() {
+    let manifest = Path::new(env!("CARGO_TARGET_TMPDIR")).join("mismatched.toml");
+    fs::write(
+        &manifest,
+        format!(
+            "\"{}/puzzles/1.1-line_dance.txt\" = 3\n",
+            env!("CARGO_MANIFEST_DIR")
+        ),
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_anima_solver"))
+        .arg("--check")
+        .arg(&manifest)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    assert!(!output.status.success());
+    assert!(stdout.contains("expected a solution of length 3 but found a solution of length 2"));
+    assert!(stdout.contains("Check: 0 of 1 puzzles matched"));
+}
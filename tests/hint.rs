@@ -0,0 +1,42 @@
+mod common;
+
+use anima_solver::*;
+
+#[test]
+fn following_hints_solves_optimally() {
+    let (mut state, data) = common::load("2.5-square_dance.txt");
+    let optimal = solve(state.clone(), &data).solution().unwrap().len();
+
+    let mut moves = 0;
+    while let Some(direction) = hint(&state, &data) {
+        state = state.transition(&data, direction);
+        moves += 1;
+        assert!(moves <= optimal);
+    }
+
+    assert!(data.is_solved_by(&state));
+    assert_eq!(moves, optimal);
+}
+
+#[test]
+fn hint_reports_distance_and_limits() {
+    let (state, data) = State::parse("..r\n\nR 0 0\n").unwrap();
+
+    assert_eq!(
+        hint_with_config(&state, &data, &SolverConfig::default()),
+        Hint::Move {
+            direction: Direction::Right,
+            distance: 2,
+        }
+    );
+    let config = SolverConfig {
+        max_expanded: Some(1),
+        ..SolverConfig::default()
+    };
+    assert_eq!(
+        hint_with_config(&state, &data, &config),
+        Hint::Aborted {
+            reason: AbortReason::NodeLimit
+        }
+    );
+}
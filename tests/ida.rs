@@ -0,0 +1,55 @@
+mod common;
+
+use anima_solver::*;
+use std::fs;
+
+#[test]
+fn ida_matches_astar_on_bundled_puzzles() {
+    for path in common::bundled_puzzles() {
+        let name = path.file_name().unwrap().to_str().unwrap();
+        // These take iterative deepening too long for a test
+        if name.starts_with("3.12-") || name.starts_with("3.14-") {
+            continue;
+        }
+
+        let (state, data) = State::parse(&fs::read_to_string(&path).unwrap()).unwrap();
+        let expected = solve(state.clone(), &data).solution().map(<[_]>::len);
+        let result = solve_ida(state.clone(), &data);
+        assert_eq!(result.solution().map(<[_]>::len), expected, "{}", name);
+        if let Some(solution) = result.solution() {
+            assert!(
+                matches!(
+                    verify(&state, &data, solution),
+                    VerifyResult::Solved { extra_moves: 0, .. }
+                ),
+                "{}",
+                name
+            );
+        }
+    }
+}
+
+#[test]
+fn ida_reports_unsolvable_puzzles() {
+    let (state, data) = State::parse("b..r\n\nR 1 0\nB 2 0\n").unwrap();
+    let config = SolverConfig::with_rules(StandardRules::new().swaps(SwapCollision::Revert));
+
+    assert!(matches!(
+        solve_ida_with_config(state, &data, &config).outcome,
+        SolveOutcome::Unsolvable { .. }
+    ));
+}
+
+#[test]
+fn ida_ignores_the_state_limit() {
+    let (state, data) = State::parse("..r\n\nR 0 0\n").unwrap();
+    let config = SolverConfig {
+        max_states: Some(0),
+        ..SolverConfig::default()
+    };
+
+    assert_eq!(
+        solve_ida_with_config(state, &data, &config).solution(),
+        Some(&[Direction::Right, Direction::Right][..])
+    );
+}
@@ -0,0 +1,24 @@
+use std::process::Command;
+
+#[test]
+fn json_output_has_one_object_per_puzzle() {
+    let output = Command::new(env!("CARGO_BIN_EXE_anima_solver"))
+        .args(["--format", "json"])
+        .arg(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/puzzles/1.1-line_dance.txt"
+        ))
+        .arg("missing.txt")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines = stdout.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("{\"path\":"));
+    assert!(lines[0].contains(
+        "\"outcome\":\"solved\",\"length\":2,\"moves\":[\"Right\",\"Right\"],\"expanded\":"
+    ));
+    assert!(lines[0].ends_with('}'));
+    assert!(lines[1].starts_with("{\"path\":\"missing.txt\",\"outcome\":\"error\",\"error\":"));
+}
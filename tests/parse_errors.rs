@@ -0,0 +1,45 @@
+use anima_solver::*;
+
+fn message(source: &str) -> String {
+    State::parse(source).unwrap_err().to_string()
+}
+
+#[test]
+fn unexpected_character_points_at_the_character() {
+    assert_eq!(
+        message("...\n.x.\n\nR 0 0\n"),
+        concat!(
+            "line 2, column 2: unexpected character 'x', expected one of '.', ' ', '~', '>', '^', ",
+            "'<', 'v', 'r', 'b', 'g', 'y' or a teleporter digit\n",
+            "  |\n",
+            "2 | .x.\n",
+            "  |  ^",
+        )
+    );
+}
+
+#[test]
+fn uneven_rows_point_past_the_short_row() {
+    assert_eq!(
+        message("...\n..\n\nR 0 0\n"),
+        concat!(
+            "line 2, column 3: row is 2 characters wide but the first row is 3 characters wide\n",
+            "  |\n",
+            "2 | ..\n",
+            "  |   ^",
+        )
+    );
+}
+
+#[test]
+fn invalid_actor_y_points_at_the_coordinate() {
+    assert_eq!(
+        message("...\n\nR 0 0\nB 1 y\n"),
+        concat!(
+            "line 4, column 5: invalid actor y coordinate: invalid digit found in string\n",
+            "  |\n",
+            "4 | B 1 y\n",
+            "  |     ^",
+        )
+    );
+}
@@ -0,0 +1,43 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+fn play(puzzle: &str, input: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_anima_solver"))
+        .arg("play")
+        .arg(format!("{}/puzzles/{}", env!("CARGO_MANIFEST_DIR"), puzzle))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn play_announces_solution() {
+    let output = play("1.1-line_dance.txt", "d\n\x1b[C\n");
+    assert!(output.contains("Solved in 2 moves!"));
+}
+
+#[test]
+fn play_undo_and_redo() {
+    let output = play("1.1-line_dance.txt", "dd\nu\nr\nrestart\nu\nq\n");
+    assert_eq!(output.matches("Solved in 2 moves!").count(), 2);
+    assert!(output.contains("Nothing to undo"));
+}
+
+#[test]
+fn play_rejects_unknown_input() {
+    let output = play("1.1-line_dance.txt", "dx\n");
+    assert!(output.contains("Unrecognized input 'x'"));
+    assert!(!output.contains("Solved"));
+}
@@ -0,0 +1,29 @@
+use anima_solver::*;
+
+fn puzzle() -> (State, Data) {
+    State::parse("r..\n..b\n\nR 0 1\nB 1 0\n").unwrap()
+}
+
+#[test]
+fn render_highlights_actors_on_their_goals() {
+    let (state, data) = puzzle();
+    assert_eq!(
+        Renderer::new().to_string(&state, &data),
+        "#####\n#*..#\n#.Bb#\n#####\n"
+    );
+    assert_eq!(
+        Renderer::new()
+            .highlight_goals(false)
+            .to_string(&state, &data),
+        "#####\n#R..#\n#.Bb#\n#####\n"
+    );
+}
+
+#[test]
+fn render_axes() {
+    let (state, data) = puzzle();
+    assert_eq!(
+        Renderer::new().axes(true).to_string(&state, &data),
+        "  #####\n1 #*..#\n0 #.Bb#\n  #####\n   012\n"
+    );
+}
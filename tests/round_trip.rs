@@ -0,0 +1,63 @@
+mod common;
+
+use anima_solver::*;
+use std::fs;
+
+#[test]
+fn bundled_puzzles_round_trip() {
+    let mut count = 0;
+    for path in common::bundled_puzzles() {
+        let (state, data) = State::parse(&fs::read_to_string(&path).unwrap()).unwrap();
+        let written = state.to_puzzle_string(&data);
+        let (reparsed_state, reparsed_data) = State::parse(&written)
+            .unwrap_or_else(|e| panic!("{} failed to re-parse: {:?}", path.display(), e));
+
+        assert_eq!(state, reparsed_state, "{}", path.display());
+        assert_eq!(data, reparsed_data, "{}", path.display());
+        assert_eq!(written, reparsed_state.to_puzzle_string(&reparsed_data));
+        count += 1;
+    }
+
+    assert!(count > 0);
+}
+
+#[test]
+fn built_puzzle_round_trips() {
+    // Goals are added bottom row first, unlike the parser which reads the top row first
+    let mut builder = PuzzleBuilder::new(Vec2::new(3, 2));
+    builder
+        .tile(Vec2::new(1, 1), Tile::Impassable)
+        .goal(Vec2::new(2, 0), Color::Red)
+        .goal(Vec2::new(0, 1), Color::Blue)
+        .goal(Vec2::new(2, 1), Color::Red)
+        .actor(Vec2::new(0, 0), Color::Red)
+        .actor(Vec2::new(1, 0), Color::Blue)
+        .actor(Vec2::new(2, 1), Color::Red);
+    let (state, data) = builder.build().unwrap();
+
+    let written = state.to_puzzle_string(&data);
+    assert_eq!(written, "b r\n..r\n\nR 0 0\nB 1 0\nR 2 1\n");
+    assert_eq!(State::parse(&written).unwrap(), (state, data));
+}
+
+#[test]
+fn goals_on_special_tiles_are_rejected() {
+    // Puzzle files write the goal in place of the tile, so the ice would be lost
+    let mut builder = PuzzleBuilder::new(Vec2::new(3, 1));
+    builder
+        .tile(Vec2::new(1, 0), Tile::Ice)
+        .goal(Vec2::new(1, 0), Color::Red)
+        .actor(Vec2::new(0, 0), Color::Red);
+
+    match builder.build() {
+        Err(BuildError::Invalid(diagnostics)) => assert!(
+            diagnostics.contains(&Diagnostic::GoalOnSpecialTile {
+                position: Vec2::new(1, 0),
+                tile: Tile::Ice,
+            }),
+            "{:?}",
+            diagnostics
+        ),
+        result => panic!("expected a validation error, got {:?}", result),
+    }
+}
@@ -0,0 +1,57 @@
+use anima_solver::*;
+
+/// Every actor moves with the player, and colliding actors are not resolved at all.
+struct Parallel;
+
+impl Rules for Parallel {
+    fn movement(&self, _: Color, direction: Direction) -> Direction {
+        direction
+    }
+
+    fn resolve_collisions(&self, _: &[Actor], _: &mut [Actor]) {}
+}
+
+fn mirrored_pair() -> (State, Data) {
+    let mut builder = PuzzleBuilder::new(Vec2::new(3, 1));
+    builder
+        .actor(Vec2::new(0, 0), Color::Red)
+        .actor(Vec2::new(2, 0), Color::Blue)
+        .goal(Vec2::new(1, 0), Color::Red);
+    builder.build().unwrap()
+}
+
+#[test]
+fn standard_rules_match_default_transitions() {
+    let (state, data) = mirrored_pair();
+    for direction in Direction::ALL {
+        assert_eq!(
+            state.transition_with(&data, &StandardRules::new(), direction),
+            state.transition(&data, direction)
+        );
+    }
+}
+
+#[test]
+fn custom_rules_change_movement_and_collisions() {
+    let (state, data) = mirrored_pair();
+
+    // Under the standard rules the red and blue actors collide in the middle and both stay put
+    assert_eq!(state.transition(&data, Direction::Right), state);
+
+    let moved = state.transition_with(&data, &Parallel, Direction::Right);
+    let positions = moved
+        .actors()
+        .iter()
+        .map(|a| (a.position(), a.color()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        positions,
+        [
+            (Vec2::new(1, 0), Color::Red),
+            (Vec2::new(2, 0), Color::Blue)
+        ]
+    );
+
+    let result = solve_with_config(state, &data, &SolverConfig::with_rules(Parallel));
+    assert_eq!(result.solution(), Some(&[Direction::Right][..]));
+}
@@ -0,0 +1,51 @@
+use anima_solver::*;
+
+#[test]
+fn parse_printed_solution() {
+    assert_eq!(
+        parse_solution("Right, Up, Left, Down").unwrap(),
+        Direction::ALL
+    );
+    assert_eq!(
+        parse_solution("right UP left, down").unwrap(),
+        Direction::ALL
+    );
+    assert_eq!(parse_solution("R, u, L, d").unwrap(), Direction::ALL);
+    assert_eq!(parse_solution("").unwrap(), []);
+}
+
+#[test]
+fn parse_compact_solution() {
+    assert_eq!(parse_solution(">^<v").unwrap(), Direction::ALL);
+    assert_eq!(parse_solution(">^ <V").unwrap(), Direction::ALL);
+    assert_eq!(parse_solution("RuLd").unwrap(), Direction::ALL);
+    assert_eq!(
+        parse_solution("RRUL").unwrap(),
+        [
+            Direction::Right,
+            Direction::Right,
+            Direction::Up,
+            Direction::Left
+        ]
+    );
+}
+
+#[test]
+fn parse_invalid_solution() {
+    assert_eq!(parse_solution("Right, Upp").unwrap_err().token(), "Upp");
+    assert_eq!(parse_solution(">^x").unwrap_err().token(), ">^x");
+}
+
+#[test]
+fn format_round_trips() {
+    assert_eq!(format_solution(&Direction::ALL), "Right, Up, Left, Down");
+    assert_eq!(format_compact(&Direction::ALL), ">^<v");
+    assert_eq!(
+        parse_solution(&format_solution(&Direction::ALL)).unwrap(),
+        Direction::ALL
+    );
+    assert_eq!(
+        parse_solution(&format_compact(&Direction::ALL)).unwrap(),
+        Direction::ALL
+    );
+}
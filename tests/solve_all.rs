@@ -0,0 +1,65 @@
+mod common;
+
+use anima_solver::*;
+
+#[test]
+fn solve_all_counts_optimal_solutions() {
+    let (state, data) = common::load("2.5-square_dance.txt");
+    let result = solve_all(state.clone(), &data, 2);
+    let optimal = result.optimal().unwrap();
+
+    assert_eq!(optimal.count, 4);
+    assert_eq!(optimal.length, 12);
+    assert_eq!(optimal.solutions.len(), 2);
+    for solution in optimal.solutions.iter() {
+        assert_eq!(
+            verify(&state, &data, solution),
+            VerifyResult::Solved {
+                step: 12,
+                extra_moves: 0
+            }
+        );
+    }
+}
+
+#[test]
+fn solve_all_counts_without_listing_solutions() {
+    let (state, data) = common::load("2.5-square_dance.txt");
+    let result = solve_all(state, &data, 0);
+    let optimal = result.optimal().unwrap();
+
+    assert_eq!(optimal.count, 4);
+    assert_eq!(optimal.length, 12);
+    assert!(optimal.solutions.is_empty());
+}
+
+#[test]
+fn solve_all_respects_limits() {
+    let (state, data) = common::load("2.5-square_dance.txt");
+    let config = SolverConfig {
+        max_expanded: Some(10),
+        ..SolverConfig::default()
+    };
+
+    let result = solve_all_with_config(state, &data, &config, 1);
+    assert_eq!(
+        result.outcome,
+        SolveAllOutcome::Aborted {
+            reason: AbortReason::NodeLimit
+        }
+    );
+    assert_eq!(result.statistics.expanded, 10);
+}
+
+#[test]
+fn solve_all_reports_unsolvable_puzzles() {
+    let (state, data) = State::parse("b..r\n\nR 1 0\nB 2 0\n").unwrap();
+    let config = SolverConfig::with_rules(StandardRules::new().swaps(SwapCollision::Revert));
+
+    assert_eq!(
+        solve_all_with_config(state, &data, &config, 1).outcome,
+        SolveAllOutcome::Unsolvable {
+            reachable_states: 2
+        }
+    );
+}
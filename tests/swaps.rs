@@ -0,0 +1,132 @@
+use anima_solver::*;
+
+fn rules(swaps: SwapCollision) -> StandardRules {
+    StandardRules::new().swaps(swaps)
+}
+
+fn positions(state: &State) -> Vec<(Color, Vec2)> {
+    let mut result = state
+        .actors()
+        .iter()
+        .map(|a| (a.color(), a.position()))
+        .collect::<Vec<_>>();
+    result.sort();
+    result
+}
+
+/// A red and a blue actor facing each other, with a second red actor free to move above them.
+fn facing_pair() -> (State, Data) {
+    State::parse("....r\n.....\n\nR 1 0\nB 2 0\nR 0 1\n").unwrap()
+}
+
+#[test]
+fn swapping_actors_pass_through_by_default() {
+    let (state, data) = facing_pair();
+    let moved = state.transition(&data, Direction::Right);
+    assert_eq!(
+        moved,
+        state.transition_with(&data, &rules(SwapCollision::PassThrough), Direction::Right)
+    );
+    assert_eq!(
+        positions(&moved),
+        [
+            (Color::Red, Vec2::new(1, 1)),
+            (Color::Red, Vec2::new(2, 0)),
+            (Color::Blue, Vec2::new(1, 0)),
+        ]
+    );
+}
+
+#[test]
+fn swapping_actors_revert() {
+    let (state, data) = facing_pair();
+    let moved = state.transition_with(&data, &rules(SwapCollision::Revert), Direction::Right);
+    assert_eq!(
+        positions(&moved),
+        [
+            (Color::Red, Vec2::new(1, 0)),
+            (Color::Red, Vec2::new(1, 1)),
+            (Color::Blue, Vec2::new(2, 0)),
+        ]
+    );
+}
+
+#[test]
+fn swapping_actors_block_the_move() {
+    let (state, data) = facing_pair();
+    let moved = state.transition_with(&data, &rules(SwapCollision::Block), Direction::Right);
+    assert_eq!(positions(&moved), positions(&state));
+
+    // Moves without a swap are unaffected
+    let moved = state.transition_with(&data, &rules(SwapCollision::Block), Direction::Up);
+    assert_eq!(
+        positions(&moved),
+        [
+            (Color::Red, Vec2::new(0, 1)),
+            (Color::Red, Vec2::new(1, 1)),
+            (Color::Blue, Vec2::new(2, 0)),
+        ]
+    );
+}
+
+#[test]
+fn swaps_change_solvability() {
+    // The actors can only reach their goals by passing through each other
+    let (state, data) = State::parse("b..r\n\nR 1 0\nB 2 0\n").unwrap();
+
+    let solve_with = |swaps| {
+        solve_with_config(
+            state.clone(),
+            &data,
+            &SolverConfig::with_rules(rules(swaps)),
+        )
+        .outcome
+    };
+    assert_eq!(
+        solve_with(SwapCollision::PassThrough),
+        SolveOutcome::Solved(vec![Direction::Right, Direction::Right])
+    );
+    // Only the initial state and the one with both actors moved apart are reachable
+    assert_eq!(
+        solve_with(SwapCollision::Revert),
+        SolveOutcome::Unsolvable {
+            reachable_states: 2
+        }
+    );
+    assert_eq!(
+        solve_with(SwapCollision::Block),
+        SolveOutcome::Unsolvable {
+            reachable_states: 2
+        }
+    );
+}
+
+#[test]
+fn blocking_ignores_other_collisions() {
+    // The actor at (1, 0) swaps with the blue actor, which also collides with the actor at (0, 0)
+    let (state, data) = State::parse("...\n...\n\nR 0 0\nR 0 1\nR 1 0\nB 2 0\n").unwrap();
+    let moved = state.transition_with(&data, &rules(SwapCollision::Block), Direction::Right);
+    assert_eq!(positions(&moved), positions(&state));
+}
+
+#[test]
+fn verify_and_play_follow_the_rules() {
+    let (state, data) = State::parse("b..r\n\nR 1 0\nB 2 0\n").unwrap();
+    let moves = [Direction::Right, Direction::Right];
+
+    assert_eq!(
+        verify_with_rules(&state, &data, &rules(SwapCollision::PassThrough), &moves),
+        VerifyResult::Solved {
+            step: 2,
+            extra_moves: 0
+        }
+    );
+    assert_eq!(
+        verify_with_rules(&state, &data, &rules(SwapCollision::Revert), &moves),
+        VerifyResult::Incomplete
+    );
+
+    let mut game = Game::with_rules(state.clone(), data, rules(SwapCollision::Block));
+    game.play(Direction::Right);
+    assert_eq!(game.state(), &state);
+}
@@ -0,0 +1,169 @@
+use anima_solver::*;
+use std::collections::{HashMap, VecDeque};
+
+/// Moves a single red actor along a one-row board and returns where it ends up.
+fn move_red(row: &str, x: i32, direction: Direction) -> i32 {
+    let (state, data) = State::parse(&format!("{}\n\nR {} 0\n", row, x)).unwrap();
+    state.transition(&data, direction).actors()[0].position().x
+}
+
+#[test]
+fn parse_and_write_tiles() {
+    let source = "r~>^<v\n0..1.0\n\nR 1 0\n";
+    let (state, data) = State::parse(source).unwrap();
+
+    assert_eq!(data.tile(Vec2::new(1, 1)), Tile::Ice);
+    assert_eq!(data.tile(Vec2::new(2, 1)), Tile::OneWay(Direction::Right));
+    assert_eq!(data.tile(Vec2::new(5, 1)), Tile::OneWay(Direction::Down));
+    assert_eq!(data.tile(Vec2::new(3, 0)), Tile::Teleporter(1));
+    assert_eq!(state.to_puzzle_string(&data), source);
+    assert_eq!(
+        Renderer::new().to_string(&state, &data),
+        "########\n#r~>^<v#\n#0R.1.0#\n########\n"
+    );
+}
+
+#[test]
+fn parse_error_lists_tiles() {
+    let message = State::parse("..x\n\nR 0 0\n").unwrap_err().to_string();
+    assert!(message.contains("'~', '>', '^', '<', 'v'"), "{}", message);
+    assert!(message.contains("teleporter digit"), "{}", message);
+}
+
+#[test]
+fn actors_slide_on_ice() {
+    assert_eq!(move_red(".~~~.", 0, Direction::Right), 4);
+    assert_eq!(move_red(".~~ .", 0, Direction::Right), 2);
+    assert_eq!(move_red("..~~.", 2, Direction::Left), 1);
+}
+
+#[test]
+fn sliding_actors_stop_at_other_actors() {
+    // Green moves down, off the board, so it stays on the ice in red's way
+    let (state, data) = State::parse(".~~~.\n\nR 0 0\nG 2 0\n").unwrap();
+    let (expected, _) = State::parse(".~~~.\n\nR 1 0\nG 2 0\n").unwrap();
+    assert_eq!(state.transition(&data, Direction::Right), expected);
+}
+
+#[test]
+fn actors_crossing_on_ice_follow_the_swap_rule() {
+    let (state, data) = State::parse(".~~~.\n\nR 0 0\nB 3 0\n").unwrap();
+    let positions = |swaps| {
+        let rules = StandardRules::new().swaps(swaps);
+        let moved = state.transition_with(&data, &rules, Direction::Right);
+        moved
+            .actors()
+            .iter()
+            .map(|a| (a.color(), a.position().x))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        positions(SwapCollision::PassThrough),
+        [(Color::Blue, 0), (Color::Red, 4)]
+    );
+    for swaps in [SwapCollision::Revert, SwapCollision::Block] {
+        assert_eq!(
+            positions(swaps),
+            [(Color::Red, 1), (Color::Blue, 2)],
+            "{:?}",
+            swaps
+        );
+    }
+}
+
+#[test]
+fn one_way_tiles_are_entered_in_their_direction() {
+    assert_eq!(move_red(".>.", 0, Direction::Right), 1);
+    assert_eq!(move_red(".>.", 2, Direction::Left), 2);
+    assert_eq!(move_red(".>.", 1, Direction::Left), 0);
+}
+
+#[test]
+fn teleporters_send_actors_to_their_partner() {
+    assert_eq!(move_red("0..0.", 1, Direction::Left), 3);
+    assert_eq!(move_red("0..0.", 3, Direction::Right), 4);
+    // A teleporter without a partner is an ordinary tile
+    assert_eq!(move_red("0..1.", 1, Direction::Left), 0);
+}
+
+#[test]
+fn unpaired_teleporters_are_reported() {
+    let (state, data) = State::parse("0.1.1\n...r.\n\nR 1 0\n").unwrap();
+    assert_eq!(
+        validate(&state, &data),
+        [Diagnostic::UnpairedTeleporter {
+            position: Vec2::new(0, 1),
+            number: 0,
+        }]
+    );
+}
+
+#[test]
+fn heuristic_is_admissible_with_tiles() {
+    let (initial_state, data) =
+        State::parse("b~~~.0\n.  >..\n0~..v \n.<..1r\n1.....\n\nR 0 0\nB 2 1\nR 5 3\n").unwrap();
+
+    let distances = distances_to_solution(&initial_state, &data);
+    for (state, &distance) in distances.iter() {
+        assert!(state.heuristic(&data) <= distance, "{:?}", state);
+    }
+
+    let result = solve(initial_state.clone(), &data);
+    assert_eq!(
+        result.solution().map(<[_]>::len),
+        distances.get(&initial_state).copied()
+    );
+}
+
+/// Finds the optimal solution length from every solvable state reachable from `initial_state`.
+fn distances_to_solution(initial_state: &State, data: &Data) -> HashMap<State, usize> {
+    let mut states = vec![initial_state.clone()];
+    let mut indices = HashMap::new();
+    indices.insert(initial_state.clone(), 0);
+    let mut predecessors = vec![Vec::new()];
+    let mut i = 0;
+    while i < states.len() {
+        for direction in Direction::ALL {
+            let next = states[i].transition(data, direction);
+            let index = *indices.entry(next.clone()).or_insert_with(|| {
+                states.push(next);
+                predecessors.push(Vec::new());
+                states.len() - 1
+            });
+            predecessors[index].push(i);
+        }
+        i += 1;
+    }
+
+    // Search backwards from every solved state
+    let mut distances = vec![usize::MAX; states.len()];
+    let mut queue = VecDeque::new();
+    for (index, state) in states.iter().enumerate() {
+        if data.is_solved_by(state) {
+            distances[index] = 0;
+            queue.push_back(index);
+        }
+    }
+    while let Some(index) = queue.pop_front() {
+        for &predecessor in predecessors[index].iter() {
+            if distances[predecessor] == usize::MAX {
+                distances[predecessor] = distances[index] + 1;
+                queue.push_back(predecessor);
+            }
+        }
+    }
+
+    states
+        .into_iter()
+        .zip(distances)
+        .filter(|&(_, distance)| distance != usize::MAX)
+        .collect()
+}
+
+#[test]
+fn actors_far_off_the_board_stay_put() {
+    let (state, data) = State::parse("...\n.r.\n\nR 2147483647 0\n").unwrap();
+    assert_eq!(state.heuristic(&data), usize::MAX);
+    assert_eq!(state.transition(&data, Direction::Right), state);
+}
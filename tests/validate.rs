@@ -0,0 +1,95 @@
+use anima_solver::*;
+
+fn diagnostics(source: &str) -> Vec<Diagnostic> {
+    let (state, data) = State::parse(source).unwrap();
+    validate(&state, &data)
+}
+
+#[test]
+fn actors_off_the_board() {
+    assert_eq!(
+        diagnostics("..r\n\nR 3 0\n"),
+        [
+            Diagnostic::ActorOutOfBounds {
+                position: Vec2::new(3, 0)
+            },
+            Diagnostic::UnreachableGoal {
+                position: Vec2::new(2, 0),
+                color: Color::Red,
+            },
+        ]
+    );
+}
+
+#[test]
+fn actors_far_off_the_board() {
+    assert_eq!(
+        diagnostics("...\n.r.\n\nR 2147483647 0\n"),
+        [
+            Diagnostic::ActorOutOfBounds {
+                position: Vec2::new(i32::MAX, 0)
+            },
+            Diagnostic::UnreachableGoal {
+                position: Vec2::new(1, 0),
+                color: Color::Red,
+            },
+        ]
+    );
+}
+
+#[test]
+fn actors_on_impassable_tiles() {
+    assert_eq!(
+        diagnostics(". r\n\nR 1 0\n"),
+        [Diagnostic::ActorOnImpassableTile {
+            position: Vec2::new(1, 0)
+        }]
+    );
+}
+
+#[test]
+fn overlapping_actors() {
+    assert_eq!(
+        diagnostics("..r\n\nR 0 0\nR 0 0\n"),
+        [Diagnostic::OverlappingActors {
+            position: Vec2::new(0, 0)
+        }]
+    );
+}
+
+#[test]
+fn not_enough_actors() {
+    assert_eq!(
+        diagnostics("r.r\n\nR 1 0\n"),
+        [Diagnostic::NotEnoughActors {
+            color: Color::Red,
+            goals: 2,
+            actors: 1,
+        }]
+    );
+}
+
+#[test]
+fn unreachable_goals() {
+    assert_eq!(
+        diagnostics("r .\n\nR 2 0\n"),
+        [Diagnostic::UnreachableGoal {
+            position: Vec2::new(0, 0),
+            color: Color::Red,
+        }]
+    );
+}
+
+#[test]
+fn puzzles_without_goals() {
+    let found = diagnostics("...\n\nR 0 0\n");
+    assert_eq!(found, [Diagnostic::NoGoals]);
+    assert_eq!(found[0].severity(), Severity::Warning);
+}
+
+#[test]
+fn puzzles_that_start_solved() {
+    let found = diagnostics("r..\n\nR 0 0\n");
+    assert_eq!(found, [Diagnostic::AlreadySolved]);
+    assert_eq!(found[0].severity(), Severity::Warning);
+}
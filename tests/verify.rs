@@ -0,0 +1,40 @@
+mod common;
+
+use anima_solver::*;
+
+#[test]
+fn verify_exact_solution() {
+    let (state, data) = common::load("1.1-line_dance.txt");
+    assert_eq!(
+        verify(&state, &data, &[Direction::Right, Direction::Right]),
+        VerifyResult::Solved {
+            step: 2,
+            extra_moves: 0
+        }
+    );
+}
+
+#[test]
+fn verify_extra_moves() {
+    let (state, data) = common::load("1.1-line_dance.txt");
+    assert_eq!(
+        verify(
+            &state,
+            &data,
+            &[Direction::Right, Direction::Right, Direction::Left]
+        ),
+        VerifyResult::Solved {
+            step: 2,
+            extra_moves: 1
+        }
+    );
+}
+
+#[test]
+fn verify_incomplete() {
+    let (state, data) = common::load("1.1-line_dance.txt");
+    assert_eq!(
+        verify(&state, &data, &[Direction::Right, Direction::Left]),
+        VerifyResult::Incomplete
+    );
+}